@@ -0,0 +1,246 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+};
+
+/// Whether allocations are counted, which takes building with the
+/// `count-allocs` feature
+pub const ENABLED: bool = cfg!(feature = "count-allocs");
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Allocator that hands every request to the system allocator and counts it
+/// for the thread that made it
+///
+/// Memory freed on another thread than the one that allocated it is counted
+/// on the freeing thread, which the solvers never do.
+pub struct Counting;
+
+/// Running totals of the current thread since the innermost [`measure`]
+#[derive(Clone, Copy)]
+struct Counters {
+    count: u64,
+    bytes: u64,
+    /// Allocated minus freed, negative after freeing older memory
+    live: i64,
+    peak: i64,
+}
+
+const ZERO: Counters = Counters {
+    count: 0,
+    bytes: 0,
+    live: 0,
+    peak: 0,
+};
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(ZERO) };
+}
+
+/// Update the counters of the current thread, unless it is being torn down
+fn record(count: u64, allocated: usize, freed: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        c.count += count;
+        c.bytes += allocated as u64;
+        c.live += allocated as i64 - freed as i64;
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(1, layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(1, layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, 0, layout.size());
+    }
+
+    /// Counted as a new allocation replacing the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(1, new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Memory allocated by one piece of code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocs {
+    /// Number of allocations, growing or shrinking one included
+    pub count: u64,
+    /// Bytes requested over all the allocations
+    pub bytes: u64,
+    /// Most bytes held at once on top of what was held before
+    pub peak: u64,
+}
+
+impl fmt::Display for Allocs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+/// Run `f` and count what it allocated on the current thread, `None` when
+/// allocations are not counted
+///
+/// Measures nest, an outer one counting everything the inner ones did.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocs>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+
+    let restore = Restore {
+        outer: COUNTERS.with(|counters| counters.replace(ZERO)),
+    };
+    let result = f();
+    let inner = COUNTERS.with(Cell::get);
+    drop(restore);
+
+    let allocs = Allocs {
+        count: inner.count,
+        bytes: inner.bytes,
+        peak: inner.peak as u64,
+    };
+    (result, Some(allocs))
+}
+
+/// Puts back the counters of an outer [`measure`] with what the inner one
+/// counted added, also when the measured code panics
+struct Restore {
+    outer: Counters,
+}
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let outer = self.outer;
+        let _ = COUNTERS.try_with(|counters| {
+            let inner = counters.get();
+            counters.set(Counters {
+                count: outer.count + inner.count,
+                bytes: outer.bytes + inner.bytes,
+                live: outer.live + inner.live,
+                peak: outer.peak.max(outer.live + inner.peak),
+            });
+        });
+    }
+}
+
+/// Byte count with a binary unit, to three significant digits
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    match value {
+        v if v < 10.0 => format!("{:.2} {}", v, UNITS[unit]),
+        v if v < 100.0 => format!("{:.1} {}", v, UNITS[unit]),
+        v => format!("{:.0} {}", v, UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(200 * 1024 * 1024), "200 MiB");
+    }
+
+    #[cfg(feature = "count-allocs")]
+    #[test]
+    fn counts_allocations() {
+        let (_, allocs) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(100);
+            v.push(1);
+            drop(v);
+            vec![0u8; 10]
+        });
+        assert_eq!(
+            allocs,
+            Some(Allocs {
+                count: 2,
+                bytes: 810,
+                peak: 800
+            })
+        );
+
+        let (_, outer) = measure(|| {
+            let held = vec![0u8; 1000];
+            let (_, inner) = measure(|| vec![0u8; 500]);
+            assert_eq!(inner.unwrap().peak, 500);
+            held
+        });
+        let outer = outer.unwrap();
+        assert_eq!((outer.count, outer.bytes, outer.peak), (2, 1500, 1500));
+    }
+
+    #[cfg(feature = "count-allocs")]
+    #[test]
+    fn counts_past_a_panic() {
+        use std::panic;
+
+        let (_, outer) = measure(|| {
+            let held = vec![0u8; 1000];
+            let result = panic::catch_unwind(|| {
+                measure(|| {
+                    let _lost = vec![0u8; 500];
+                    // Unlike `panic!`, prints nothing
+                    panic::resume_unwind(Box::new(()));
+                })
+            });
+            assert!(result.is_err());
+            held
+        });
+
+        // Unwinding allocates a little on top of the two vectors
+        let outer = outer.unwrap();
+        assert!(outer.count >= 2);
+        assert!(outer.bytes >= 1500 && outer.peak >= 1500);
+    }
+
+    #[cfg(not(feature = "count-allocs"))]
+    #[test]
+    fn disabled() {
+        assert_eq!(measure(|| vec![1, 2, 3]), (vec![1, 2, 3], None));
+    }
+}
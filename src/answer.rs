@@ -0,0 +1,268 @@
+use std::fmt;
+
+/// Answer to one part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    Glyphs(Glyphs),
+}
+
+/// Outcome of a part that found no answer for its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoSolution;
+
+/// Result of solving one part of a puzzle
+pub type Solution = Result<Answer, NoSolution>;
+
+impl Answer {
+    /// The answer as it would be typed into the website, glyphs are read as
+    /// letters when they can be recognized
+    pub fn submission(&self) -> Option<String> {
+        match self {
+            Answer::Int(n) => Some(n.to_string()),
+            Answer::Text(s) => Some(s.clone()),
+            Answer::Glyphs(glyphs) => glyphs.decode(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Glyphs(glyphs) => write!(f, "{}", glyphs),
+        }
+    }
+}
+
+impl fmt::Display for NoSolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no solution")
+    }
+}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        match self {
+            Answer::Text(s) => s == other,
+            _ => {
+                let rendered = self.to_string();
+                rendered == other
+            }
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Int(i64::try_from(n).expect("answer does not fit in an i64"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<Glyphs> for Answer {
+    fn from(glyphs: Glyphs) -> Answer {
+        Answer::Glyphs(glyphs)
+    }
+}
+
+/// Picture of lit pixels that spells out an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glyphs {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+/// Letters of the 4x6 font used by the puzzles, drawn row by row
+const FONT: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+impl Glyphs {
+    pub fn new(width: usize, height: usize) -> Glyphs {
+        Glyphs {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, lit: bool) {
+        self.pixels[y * self.width + x] = lit;
+    }
+
+    /// Letters the font can draw
+    pub fn alphabet() -> impl Iterator<Item = char> {
+        FONT.iter().map(|&(c, _)| c)
+    }
+
+    /// Draw `text` in the puzzle font, `None` if a letter is not in it
+    pub fn render(text: &str) -> Option<Glyphs> {
+        let letters = text.chars().count();
+        let mut glyphs = Glyphs::new(
+            (letters * (GLYPH_WIDTH + 1)).saturating_sub(1),
+            GLYPH_HEIGHT,
+        );
+
+        for (i, c) in text.chars().enumerate() {
+            let (_, pattern) = FONT.iter().find(|&&(letter, _)| letter == c)?;
+
+            for (j, pixel) in pattern.chars().enumerate() {
+                let (x, y) = (i * (GLYPH_WIDTH + 1) + j % GLYPH_WIDTH, j / GLYPH_WIDTH);
+                glyphs.set(x, y, pixel == '#');
+            }
+        }
+
+        Some(glyphs)
+    }
+
+    /// Read the picture as letters, `None` if any letter is not recognized
+    pub fn decode(&self) -> Option<String> {
+        if self.height != GLYPH_HEIGHT || self.width == 0 {
+            return None;
+        }
+
+        // Letters are separated by a single blank column
+        let letter_count = (self.width + 1) / (GLYPH_WIDTH + 1);
+
+        (0..letter_count)
+            .map(|i| {
+                let left = i * (GLYPH_WIDTH + 1);
+                let pattern: String = (0..GLYPH_HEIGHT)
+                    .flat_map(|y| (left..left + GLYPH_WIDTH).map(move |x| (x, y)))
+                    .map(|(x, y)| if self.get(x, y) { '#' } else { '.' })
+                    .collect();
+
+                FONT.iter()
+                    .find(|(_, letter)| *letter == pattern)
+                    .map(|(c, _)| *c)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Glyphs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '#' } else { ' ' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyphs_from(picture: &str) -> Glyphs {
+        let rows: Vec<_> = picture.lines().collect();
+        let mut glyphs = Glyphs::new(rows[0].len(), rows.len());
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                glyphs.set(x, y, c == '#');
+            }
+        }
+
+        glyphs
+    }
+
+    #[test]
+    fn display_and_compare() {
+        assert_eq!(Answer::from(42usize), "42");
+        assert_eq!(Answer::from(-7), "-7");
+        assert_eq!(Answer::from("abc".to_string()), "abc");
+        assert_ne!(Answer::from(42usize), "43");
+        assert_eq!(NoSolution.to_string(), "no solution");
+    }
+
+    #[test]
+    fn glyphs_display() {
+        let glyphs = glyphs_from("#.\n.#");
+        assert_eq!(glyphs.to_string(), "# \n #\n");
+        assert_eq!(Answer::from(glyphs), "# \n #\n");
+    }
+
+    #[test]
+    fn glyphs_decode() {
+        let glyphs = glyphs_from(
+            "\
+#..#.####
+#..#.#...
+####.###.
+#..#.#...
+#..#.#...
+#..#.####",
+        );
+        assert_eq!(glyphs.decode().as_deref(), Some("HE"));
+        assert_eq!(Answer::from(glyphs).submission().as_deref(), Some("HE"));
+
+        let square = glyphs_from("#####\n#...#\n#####");
+        assert_eq!(square.decode(), None);
+    }
+
+    #[test]
+    fn glyphs_render() {
+        let text: String = Glyphs::alphabet().collect();
+        let glyphs = Glyphs::render(&text).unwrap();
+        assert_eq!(glyphs.width(), 16 * 5 - 1);
+        assert_eq!(glyphs.decode(), Some(text));
+
+        assert_eq!(Glyphs::render("HI"), None);
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    days::{Parsed, Puzzle},
+    ParseError,
+};
+use std::{
+    fmt,
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// Step of solving a puzzle that is timed on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn part(part: u32) -> Option<Phase> {
+        match part {
+            1 => Some(Phase::Part1),
+            2 => Some(Phase::Part2),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "1"),
+            Phase::Part2 => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Phase, String> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "1" => Ok(Phase::Part1),
+            "2" => Ok(Phase::Part2),
+            _ => Err(format!("unknown phase `{}`", s)),
+        }
+    }
+}
+
+/// How long and how often code is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Time spent running the code before measuring, also used to estimate
+    /// how long one iteration takes
+    pub warm_up: Duration,
+    /// Rough total time spent measuring
+    pub measure: Duration,
+    /// Number of timed samples, each running the same number of iterations,
+    /// fewer are taken when a single iteration overruns the measuring time
+    pub samples: usize,
+}
+
+/// Samples taken however slow the code is
+const MIN_SAMPLES: usize = 5;
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warm_up: Duration::from_millis(300),
+            measure: Duration::from_secs(2),
+            samples: 50,
+        }
+    }
+}
+
+/// Time of one iteration, summarized over every sample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    /// Iterations timed together in each sample
+    pub iterations: u64,
+    pub median: Duration,
+    pub mean: Duration,
+    pub min: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Summarize samples given as seconds per iteration
+    fn from_samples(iterations: u64, samples: &[f64]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Stats {
+            samples: n,
+            iterations,
+            median: Duration::from_secs_f64(median),
+            mean: Duration::from_secs_f64(mean),
+            min: Duration::from_secs_f64(sorted[0]),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "median {}, mean {}, min {}, std dev {} ({} x {} iterations)",
+            format_duration(self.median),
+            format_duration(self.mean),
+            format_duration(self.min),
+            format_duration(self.std_dev),
+            self.samples,
+            self.iterations
+        )
+    }
+}
+
+/// Format a duration with three significant digits in a fitting unit
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_secs_f64() * 1e9;
+
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, "ns")
+    } else if nanos < 1e6 {
+        (nanos / 1e3, "µs")
+    } else if nanos < 1e9 {
+        (nanos / 1e6, "ms")
+    } else {
+        (nanos / 1e9, "s")
+    };
+
+    let decimals = if value < 10.0 {
+        2
+    } else if value < 100.0 {
+        1
+    } else {
+        0
+    };
+    format!("{:.*} {}", decimals, value, unit)
+}
+
+/// Measure `f`, first warming up, then timing enough iterations per sample
+/// to fill the measuring time
+pub fn bench<T>(config: &BenchConfig, mut f: impl FnMut() -> T) -> Stats {
+    // Double the batch size until the warm-up time is spent, so very fast
+    // code is not dominated by reading the clock
+    let start = Instant::now();
+    let mut done = 0u64;
+    let mut batch = 1;
+    loop {
+        for _ in 0..batch {
+            black_box(f());
+        }
+        done += batch;
+        batch *= 2;
+
+        if start.elapsed() >= config.warm_up {
+            break;
+        }
+    }
+    let estimate = start.elapsed().as_secs_f64() / done as f64;
+
+    let fitting = (config.measure.as_secs_f64() / estimate) as usize;
+    let samples = fitting.clamp(MIN_SAMPLES.min(config.samples), config.samples.max(1));
+    let per_sample = config.measure.as_secs_f64() / samples as f64;
+    let iterations = (per_sample / estimate).max(1.0) as u64;
+
+    let times: Vec<f64> = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                black_box(f());
+            }
+            start.elapsed().as_secs_f64() / iterations as f64
+        })
+        .collect();
+
+    Stats::from_samples(iterations, &times)
+}
+
+/// Measure parsing a puzzle input
+pub fn bench_parse(
+    config: &BenchConfig,
+    puzzle: &Puzzle,
+    input: &str,
+) -> Result<Stats, ParseError> {
+    // Fail early instead of timing the error path
+    puzzle.parse(input)?;
+
+    Ok(bench(config, || puzzle.parse(black_box(input))))
+}
+
+/// Measure solving one part of an already parsed input, `None` if the part
+/// does not exist
+pub fn bench_part(
+    config: &BenchConfig,
+    puzzle: &Puzzle,
+    part: u32,
+    parsed: &Parsed,
+) -> Option<Stats> {
+    Phase::part(part)?;
+
+    Some(bench(config, || puzzle.run(part, black_box(parsed))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples(10, &[4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.iterations, 10);
+        assert_eq!(stats.median, Duration::from_millis(2500));
+        assert_eq!(stats.mean, Duration::from_millis(2500));
+        assert_eq!(stats.min, Duration::from_secs(1));
+        assert_eq!(stats.std_dev.as_millis(), 1290);
+
+        let stats = Stats::from_samples(1, &[3.0, 1.0, 2.0]);
+        assert_eq!(stats.median, Duration::from_secs(2));
+
+        let stats = Stats::from_samples(1, &[1.0]);
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_nanos(12)), "12.0 ns");
+        assert_eq!(format_duration(Duration::from_nanos(1_234)), "1.23 µs");
+        assert_eq!(format_duration(Duration::from_micros(456_700)), "457 ms");
+        assert_eq!(format_duration(Duration::from_secs(2)), "2.00 s");
+    }
+
+    #[test]
+    fn bench_counts_iterations() {
+        let config = BenchConfig {
+            warm_up: Duration::from_millis(10),
+            measure: Duration::from_millis(20),
+            samples: 5,
+        };
+
+        let mut calls = 0u64;
+        let stats = bench(&config, || calls += 1);
+
+        assert_eq!(stats.samples, 5);
+        assert!(stats.iterations > 1);
+        assert!(calls > stats.iterations * 5);
+        assert!(stats.min <= stats.median);
+    }
+
+    #[test]
+    fn bench_slow_code() {
+        let config = BenchConfig {
+            warm_up: Duration::from_millis(1),
+            measure: Duration::from_millis(20),
+            samples: 50,
+        };
+
+        let stats = bench(&config, || std::thread::sleep(Duration::from_millis(5)));
+
+        assert_eq!(stats.samples, MIN_SAMPLES);
+        assert_eq!(stats.iterations, 1);
+    }
+
+    #[test]
+    fn bench_puzzle_part() {
+        let config = BenchConfig {
+            warm_up: Duration::from_millis(1),
+            measure: Duration::from_millis(5),
+            samples: 3,
+        };
+        let puzzle = crate::days::get(1).unwrap();
+
+        let stats = bench_parse(&config, puzzle, "1\n2\n").unwrap();
+        assert_eq!(stats.samples, 3);
+        assert!(bench_parse(&config, puzzle, "x\n").is_err());
+
+        let parsed = puzzle.parse("1\n2\n").unwrap();
+        let stats = bench_part(&config, puzzle, 1, &parsed).unwrap();
+        assert_eq!(stats.samples, 3);
+        assert!(bench_part(&config, puzzle, 3, &parsed).is_none());
+    }
+
+    #[test]
+    fn phases() {
+        for phase in Phase::ALL {
+            assert_eq!(phase.to_string().parse(), Ok(phase));
+        }
+        assert_eq!(Phase::part(2), Some(Phase::Part2));
+        assert_eq!(Phase::part(3), None);
+        assert!("3".parse::<Phase>().is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Environment variable holding the path of the config file
+pub const CONFIG_VAR: &str = "ADVENT_CONFIG";
+
+/// Name of the config file looked up in the working directory and crate root
+pub const CONFIG_FILE: &str = "advent.conf";
+
+/// Settings read from the config file
+///
+/// The file holds one `key = value` pair per line, `#` starts a comment:
+///
+/// ```text
+/// # Where the real and test inputs live
+/// data_dir = /home/me/advent/data
+/// # Session cookie of a logged in browser, used to download inputs
+/// session = 53616c7465645f5f...
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Directory holding the `real` and `test` input folders
+    pub data_dir: Option<PathBuf>,
+    /// Session token for the puzzle website
+    pub session: Option<String>,
+    /// Base url of the puzzle website
+    pub base_url: Option<String>,
+    /// Minimum delay between requests to the puzzle website
+    pub request_delay: Option<Duration>,
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `key = value`", i + 1))?;
+            let value = value.trim();
+
+            match key.trim() {
+                "data_dir" => config.data_dir = Some(PathBuf::from(value)),
+                "session" => config.session = Some(value.to_string()),
+                "base_url" => config.base_url = Some(value.trim_end_matches('/').to_string()),
+                "request_delay" => {
+                    let delay = value
+                        .parse()
+                        .ok()
+                        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                        .ok_or_else(|| format!("line {}: expected a delay in seconds", i + 1))?;
+                    config.request_delay = Some(delay);
+                }
+                key => return Err(format!("line {}: unknown key `{}`", i + 1, key)),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Path of the config file, if there is one
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os(CONFIG_VAR) {
+            return Some(PathBuf::from(path));
+        }
+
+        [
+            PathBuf::from(CONFIG_FILE),
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(CONFIG_FILE),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+    }
+
+    /// Load the config file, the default config is used when there is none
+    pub fn load() -> io::Result<Config> {
+        let path = match Config::path() {
+            Some(path) => path,
+            None => return Ok(Config::default()),
+        };
+
+        let text = fs::read_to_string(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+
+        Config::parse(&text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let config = Config::parse("# comment\n\ndata_dir = /tmp/data # inline\n").unwrap();
+        assert_eq!(config.data_dir, Some(PathBuf::from("/tmp/data")));
+
+        let config = Config::parse(
+            "session = abc123\nbase_url = http://localhost:8080/\nrequest_delay = 1.5",
+        )
+        .unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc123"));
+        assert_eq!(config.base_url.as_deref(), Some("http://localhost:8080"));
+        assert_eq!(config.request_delay, Some(Duration::from_millis(1500)));
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Config::parse("data_dir /tmp").unwrap_err(),
+            "line 1: expected `key = value`"
+        );
+        assert_eq!(
+            Config::parse("\ncolour = blue").unwrap_err(),
+            "line 2: unknown key `colour`"
+        );
+        assert_eq!(
+            Config::parse("request_delay = soon").unwrap_err(),
+            "line 1: expected a delay in seconds"
+        );
+        for delay in ["-1", "inf", "NaN", "1e30"] {
+            assert_eq!(
+                Config::parse(&format!("\nrequest_delay = {}", delay)).unwrap_err(),
+                "line 2: expected a delay in seconds"
+            );
+        }
+    }
+}
@@ -1,90 +1,80 @@
 // https://adventofcode.com/2021/day/1
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let values = input.as_ref().lines().map(|x| x.parse::<i32>().unwrap());
-
-    let mut increments = 0;
-    let mut previous_value = None;
+use super::Solver;
+use crate::{
+    stream::{self, Fold, StreamError},
+    ParseError, Solution, Source,
+};
+use std::{collections::VecDeque, io::BufRead};
+
+pub struct Day1;
+
+/// Number of sums of `WINDOW` consecutive values larger than the sum before
+///
+/// Two neighbouring windows share all but their first and last value, so
+/// only those two are compared.
+#[derive(Debug, Default)]
+pub struct Increments<const WINDOW: usize> {
+    recent: VecDeque<i32>,
+    increments: usize,
+}
 
-    for value in values {
-        if let Some(v) = previous_value {
-            if value > v {
-                increments += 1;
+impl<const WINDOW: usize> Fold<i32> for Increments<WINDOW> {
+    fn push(&mut self, &value: &i32) {
+        if self.recent.len() == WINDOW {
+            let first = self.recent.pop_front().unwrap();
+            if value > first {
+                self.increments += 1;
             }
         }
 
-        previous_value = Some(value);
+        self.recent.push_back(value);
     }
 
-    increments.to_string()
-}
-
-pub fn part2(input: impl AsRef<str>) -> String {
-    let values = input
-        .as_ref()
-        .lines()
-        .map(|x| x.parse::<i32>().unwrap())
-        .collect::<Vec<_>>();
-    let windows = values.windows(3).map(|window| window.iter().sum::<i32>());
-
-    let mut increments = 0;
-    let mut previous_value = None;
-
-    for value in windows {
-        if let Some(v) = previous_value {
-            if value > v {
-                increments += 1;
-            }
-        }
-
-        previous_value = Some(value);
+    fn finish(self) -> Solution {
+        Ok(self.increments.into())
     }
+}
 
-    increments.to_string()
+fn parse_line<'a>(source: &Source<'a>, line: &'a str) -> Result<i32, ParseError> {
+    source.number(line)
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+impl Solver for Day1 {
+    type Input = Vec<i32>;
 
-    const DAY_NUM: u32 = 1;
+    const DAY: u32 = 1;
+    const TITLE: &str = "Sonar Sweep";
 
-    use super::*;
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(get_test(1)), "7");
+        source.parse_lines(input, |line| parse_line(&source, line))
     }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(get_test(1)), "5");
+    fn part1(values: &Vec<i32>) -> Solution {
+        Increments::<1>::fold(values)
     }
 
-    #[test]
-    fn test_part1_real() {
-        assert_eq!(part1(get_real(1)), "1466");
+    fn part2(values: &Vec<i32>) -> Solution {
+        Increments::<3>::fold(values)
     }
+}
 
-    #[test]
-    fn test_part2_real() {
-        assert_eq!(part2(get_real(1)), "1491");
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day1::solve_part1(input.as_ref())
+}
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day1::solve_part2(input.as_ref())
+}
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
-    }
+/// Solve part 1 reading the input one line at a time
+pub fn part1_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    stream::fold_lines(Day1::DAY, reader, parse_line).map(Increments::<1>::finish)
+}
+
+/// Solve part 2 reading the input one line at a time
+pub fn part2_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    stream::fold_lines(Day1::DAY, reader, parse_line).map(Increments::<3>::finish)
 }
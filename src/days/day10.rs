@@ -1,135 +1,137 @@
 // https://adventofcode.com/2021/day/10
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    input
-        .as_ref()
-        .lines()
-        .map(|line| {
-            let mut stack = vec![];
-
-            line.chars()
-                .map(|c| match c {
-                    '(' | '[' | '{' | '<' => {
-                        stack.push(c);
-                        0
-                    }
-                    ')' | ']' | '}' | '>' => {
-                        let left = stack.pop().unwrap();
-                        match (left, c) {
-                            ('(', ')') | ('[', ']') | ('{', '}') | ('<', '>') => 0,
-                            (_, ')') => 3,
-                            (_, ']') => 57,
-                            (_, '}') => 1197,
-                            (_, '>') => 25137,
-                            (_, _) => panic!("Unexpected character"),
-                        }
-                    }
-                    _ => panic!("Unexpected character"),
-                })
-                .sum::<u64>()
-        })
-        .sum::<u64>()
-        .to_string()
+use super::Solver;
+use crate::{
+    stream::{self, Fold, StreamError},
+    NoSolution, ParseError, Solution, Source,
+};
+use std::io::BufRead;
+
+pub struct Day10;
+
+fn parse_line<'a>(source: &Source<'a>, line: &'a str) -> Result<String, ParseError> {
+    source.check_chars(line, |c| "()[]{}<>".contains(c), "expected a bracket")?;
+    Ok(line.to_owned())
 }
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let lines: Vec<_> = input.as_ref().lines().collect();
+/// Check the brackets of a line, giving the first one closing the wrong
+/// chunk or else the chunks left open
+fn check(line: &str) -> Result<Vec<char>, char> {
+    let mut stack = vec![];
+
+    for c in line.chars() {
+        match c {
+            '(' | '[' | '{' | '<' => stack.push(c),
+            ')' | ']' | '}' | '>' => match (stack.pop(), c) {
+                (Some('('), ')') | (Some('['), ']') | (Some('{'), '}') | (Some('<'), '>') => {}
+                (_, _) => return Err(c),
+            },
+            _ => unreachable!("lines are checked while parsing"),
+        }
+    }
 
-    let mut line_score: Vec<u64> = vec![];
+    Ok(stack)
+}
 
-    'line_loop: for line in lines {
-        let mut stack = vec![];
+/// Total score of the corrupted lines
+#[derive(Debug, Default)]
+pub struct ErrorScore(u64);
+
+impl Fold<String> for ErrorScore {
+    fn push(&mut self, line: &String) {
+        self.0 += match check(line) {
+            Ok(_) => 0,
+            Err(')') => 3,
+            Err(']') => 57,
+            Err('}') => 1197,
+            Err('>') => 25137,
+            Err(_) => unreachable!(),
+        };
+    }
 
-        for c in line.chars() {
-            match c {
-                '(' | '[' | '{' | '<' => stack.push(c),
-                ')' | ']' | '}' | '>' => {
-                    let top = stack.pop().unwrap();
+    fn finish(self) -> Solution {
+        Ok(self.0.into())
+    }
+}
 
-                    match (top, c) {
-                        ('(', ')') | ('[', ']') | ('{', '}') | ('<', '>') => continue,
-                        (_, ')') | (_, ']') | (_, '}') | (_, '>') => {
-                            continue 'line_loop;
-                        }
-                        (_, _) => panic!("Unexpected character"),
+/// Scores of completing every incomplete line, of which the middle one is
+/// the answer
+#[derive(Debug, Default)]
+pub struct CompletionScores(Vec<u64>);
+
+impl Fold<String> for CompletionScores {
+    fn push(&mut self, line: &String) {
+        if let Ok(stack) = check(line) {
+            self.0.push(stack.iter().rev().fold(0, |acc, c| {
+                acc * 5
+                    + match c {
+                        '(' => 1,
+                        '[' => 2,
+                        '{' => 3,
+                        '<' => 4,
+                        _ => unreachable!(),
                     }
-                }
-                _ => panic!("Unexpected character"),
-            }
+            }));
         }
-
-        line_score.push(stack.iter().rev().fold(0, |mut acc, c| {
-            acc *= 5;
-            match c {
-                '(' => acc += 1,
-                '[' => acc += 2,
-                '{' => acc += 3,
-                '<' => acc += 4,
-                _ => panic!("Unexpected character"),
-            }
-            acc
-        }))
     }
 
-    line_score.sort_unstable();
+    fn finish(mut self) -> Solution {
+        if self.0.is_empty() {
+            return Err(NoSolution);
+        }
+
+        self.0.sort_unstable();
 
-    line_score[line_score.len() / 2].to_string()
+        Ok(self.0[self.0.len() / 2].into())
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+impl Solver for Day10 {
+    type Input = Vec<String>;
 
-    const DAY_NUM: u32 = 10;
+    const DAY: u32 = 10;
+    const TITLE: &str = "Syntax Scoring";
 
-    const TEST_PART_1_RESULT: &'static str = "26397";
-    const TEST_PART_2_RESULT: &'static str = "288957";
-    const REAL_PART_1_RESULT: &'static str = "240123";
-    const REAL_PART_2_RESULT: &'static str = "3260812321";
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-    #[test]
-    fn test_part1() {
-        let result = part1(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_1_RESULT);
-        println!("Day {} Part 1 test result: {}", DAY_NUM, result);
+        source.parse_lines(input, |line| parse_line(&source, line))
     }
 
-    #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_2_RESULT);
-        println!("Day {} Part 2 test result: {}", DAY_NUM, result);
+    fn part1(lines: &Vec<String>) -> Solution {
+        ErrorScore::fold(lines)
     }
 
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_1_RESULT);
-        println!("Day {} Part 1 real result: {}", DAY_NUM, result);
+    fn part2(lines: &Vec<String>) -> Solution {
+        CompletionScores::fold(lines)
     }
+}
 
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_2_RESULT);
-        println!("Day {} Part 2 real result: {}", DAY_NUM, result);
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day10::solve_part1(input.as_ref())
+}
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day10::solve_part2(input.as_ref())
+}
+
+/// Solve part 1 reading the input one line at a time
+pub fn part1_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    stream::fold_lines(Day10::DAY, reader, parse_line).map(ErrorScore::finish)
+}
+
+/// Solve part 2 reading the input one line at a time
+pub fn part2_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    stream::fold_lines(Day10::DAY, reader, parse_line).map(CompletionScores::finish)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
+    #[test]
+    fn test_parse_error() {
+        let error = part1("[({})]\n[(a)]\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "a"));
     }
 }
@@ -1,168 +1,141 @@
-use std::vec;
-
 // https://adventofcode.com/2021/day/11
 
-struct OctopusGrid {
-    points: Vec<u32>,
-    width: i32,
-    height: i32,
-}
+use super::{Param, Solver};
+use crate::{Grid, NoSolution, ParseError, Solution, Source};
+use std::collections::HashSet;
 
-impl OctopusGrid {
-    fn step(&mut self) -> usize {
-        for point in self.points.iter_mut() {
-            *point += 1;
-        }
+pub struct Day11;
 
-        let mut flashed = vec![false; (self.width * self.height).try_into().unwrap()];
+#[derive(Debug, Clone)]
+pub struct OctopusGrid {
+    energy: Grid<u32>,
+}
 
-        'outer_loop: loop {
-            for y in 0..self.height {
-                for x in 0..self.width {
-                    let index: usize = (self.height * y + x).try_into().unwrap();
+impl OctopusGrid {
+    /// Number of flashes over the next `steps` steps
+    fn flashes(&self, steps: usize) -> usize {
+        let mut octopus_grid = self.clone();
 
-                    if !flashed[index] && self.points[index] > 9 {
-                        flashed[index] = true;
-                        self.flash_point(x, y);
-                        continue 'outer_loop;
-                    }
-                }
-            }
+        (0..steps).map(|_| octopus_grid.step()).sum()
+    }
 
-            break;
+    fn step(&mut self) -> usize {
+        for energy in self.energy.iter_mut() {
+            *energy += 1;
         }
 
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let index: usize = (self.height * y + x).try_into().unwrap();
+        let mut flashed = Grid::new(self.energy.width(), self.energy.height(), false);
+        let mut charged: Vec<_> = self
+            .energy
+            .enumerate()
+            .filter(|(_, &energy)| energy > 9)
+            .map(|(position, _)| position)
+            .collect();
+
+        while let Some((x, y)) = charged.pop() {
+            if flashed.set(x, y, true) == Some(true) {
+                continue;
+            }
 
-                if self.points[index] >= 10 {
-                    self.points[index] = 0;
+            let neighbours: Vec<_> = self.energy.neighbours8(x, y).collect();
+            for position in neighbours {
+                self.energy[position] += 1;
+                if self.energy[position] > 9 && !flashed[position] {
+                    charged.push(position);
                 }
             }
         }
 
-        flashed.into_iter().filter(|b| *b).count()
-    }
-
-    fn flash_point(&mut self, x: i32, y: i32) {
-        let x_min = 0.max(x - 1);
-        let x_max = (self.width - 1).min(x + 1);
-
-        let y_min = 0.max(y - 1);
-        let y_max = (self.height - 1).min(y + 1);
-
-        for y in y_min..=y_max {
-            for x in x_min..=x_max {
-                let index: usize = (self.height * y + x).try_into().unwrap();
-                self.points[index] += 1;
+        for energy in self.energy.iter_mut() {
+            if *energy > 9 {
+                *energy = 0;
             }
         }
+
+        flashed.iter().filter(|&&b| b).count()
     }
 }
 
-fn parse_input(input: impl AsRef<str>) -> OctopusGrid {
-    let lines: Vec<_> = input.as_ref().lines().collect();
+impl Solver for Day11 {
+    type Input = OctopusGrid;
 
-    let height = lines.len();
-    let width = lines[0].chars().count();
+    const DAY: u32 = 11;
+    const TITLE: &str = "Dumbo Octopus";
+    const PARAMS: &'static [Param] = &[Param {
+        name: "steps",
+        part: 1,
+        default: 100,
+    }];
 
-    let points = lines
-        .into_iter()
-        .flat_map(|line| line.chars().map(|c| c.to_digit(10).unwrap()))
-        .collect();
+    fn parse(input: &str) -> Result<OctopusGrid, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-    OctopusGrid {
-        width: width.try_into().unwrap(),
-        height: height.try_into().unwrap(),
-        points,
+        Ok(OctopusGrid {
+            energy: Grid::parse_digits(&source, input)?,
+        })
     }
-}
-
-pub fn part1(input: impl AsRef<str>) -> String {
-    let mut octopus_grid = parse_input(input);
 
-    let flash_count: usize = (0..100).map(|_| octopus_grid.step()).sum();
+    fn part1(octopus_grid: &OctopusGrid) -> Solution {
+        Ok(octopus_grid.flashes(100).into())
+    }
 
-    flash_count.to_string()
-}
+    fn part2(octopus_grid: &OctopusGrid) -> Solution {
+        let mut octopus_grid = octopus_grid.clone();
+        // Each step only depends on the energy levels, so a grid that comes
+        // back to an earlier state is stuck in a cycle and never synchronizes
+        let mut seen = HashSet::new();
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let mut octopus_grid = parse_input(input);
+        let mut i = 1;
 
-    let mut i = 1;
+        loop {
+            let flashed = octopus_grid.step();
 
-    loop {
-        let flashed = octopus_grid.step();
+            if flashed == octopus_grid.energy.len() {
+                break;
+            }
+            if !seen.insert(octopus_grid.energy.clone()) {
+                return Err(NoSolution);
+            }
 
-        if flashed
-            == (octopus_grid.width * octopus_grid.height)
-                .try_into()
-                .unwrap()
-        {
-            break;
+            i += 1;
         }
 
-        i += 1;
+        Ok(i.into())
+    }
+
+    fn run_with(octopus_grid: &OctopusGrid, part: u32, values: &[usize]) -> Solution {
+        match part {
+            1 => Ok(octopus_grid.flashes(values[0]).into()),
+            _ => Self::part2(octopus_grid),
+        }
     }
+}
+
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day11::solve_part1(input.as_ref())
+}
 
-    i.to_string()
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day11::solve_part2(input.as_ref())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
-
-    const DAY_NUM: u32 = 11;
-
-    const TEST_PART_1_RESULT: &'static str = "1656";
-    const TEST_PART_2_RESULT: &'static str = "195";
-    const REAL_PART_1_RESULT: &'static str = "1773";
-    const REAL_PART_2_RESULT: &'static str = "494";
 
     #[test]
-    fn test_part1() {
-        let result = part1(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_1_RESULT);
-        println!("Day {} Part 1 test result: {}", DAY_NUM, result);
+    fn test_non_square() {
+        // Flashing does not depend on orientation, so a grid wider than it is
+        // tall must flash like its transpose
+        let input = "5483143223\n2745854711\n5264556173\n6141336146\n6357385478\n";
+        let source = Source::new(11, input);
+        let transposed = Grid::parse_digits(&source, input).unwrap().transpose();
+
+        assert_eq!(part1(input), part1(transposed.to_string()));
     }
 
     #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_2_RESULT);
-        println!("Day {} Part 2 test result: {}", DAY_NUM, result);
-    }
-
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_1_RESULT);
-        println!("Day {} Part 1 real result: {}", DAY_NUM, result);
-    }
-
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_2_RESULT);
-        println!("Day {} Part 2 real result: {}", DAY_NUM, result);
-    }
-
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
-
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
+    fn test_never_synchronizes() {
+        assert_eq!(part2("840\n913\n172\n"), Ok(Err(NoSolution)));
     }
 }
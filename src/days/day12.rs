@@ -1,185 +1,123 @@
 // https://adventofcode.com/2021/day/12
 
-use std::collections::HashMap;
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum Cave {
-    Start,
-    End,
-    Big(String),
-    Small(String),
-}
-
-impl Cave {
-    fn new(name: impl AsRef<str>) -> Cave {
-        let is_upper = name.as_ref().chars().all(|c| c.is_uppercase());
+use super::Solver;
+use crate::{
+    graph::{Graph, NodeId},
+    ParseError, Solution, Source,
+};
 
-        match (is_upper, name.as_ref()) {
-            (_, "start") => Cave::Start,
-            (_, "end") => Cave::End,
-            (true, _) => Cave::Big(name.as_ref().to_string()),
-            (false, _) => Cave::Small(name.as_ref().to_string()),
-        }
-    }
-}
+pub struct Day12;
 
 #[derive(Debug, Clone)]
-struct CaveSystem {
-    adjacency_list: HashMap<Cave, Vec<Cave>>,
+pub struct CaveSystem {
+    graph: Graph<String>,
+    /// Whether each cave is small and can only be visited once
+    small: Vec<bool>,
+    start: NodeId,
+    end: NodeId,
 }
 
 impl CaveSystem {
     fn new() -> CaveSystem {
-        CaveSystem {
-            adjacency_list: HashMap::new(),
-        }
+        let mut system = CaveSystem {
+            graph: Graph::new(),
+            small: vec![],
+            start: 0,
+            end: 0,
+        };
+        system.start = system.cave("start");
+        system.end = system.cave("end");
+        system
     }
 
-    fn insert_edge(&mut self, n1: &Cave, n2: &Cave) {
-        for (a, b) in [(n1, n2), (n2, n1)] {
-            match self.adjacency_list.get_mut(a) {
-                Some(node) => node.push(b.clone()),
-                None => {
-                    self.adjacency_list.insert(a.clone(), vec![b.clone()]);
-                }
-            }
+    fn cave(&mut self, name: &str) -> NodeId {
+        let id = self.graph.node(name.to_string());
+        if id == self.small.len() {
+            self.small.push(!name.chars().all(|c| c.is_uppercase()));
         }
+        id
     }
-}
 
-fn find_paths(
-    current: &Cave,
-    cave_system: &CaveSystem,
-    visits: &HashMap<&Cave, usize>,
-    mut can_double_visit: bool,
-) -> Vec<Vec<Cave>> {
-    let mut all_paths = vec![];
-
-    let mut visits = visits.clone();
-    let current_visits = match visits.get_mut(current) {
-        Some(v) => {
-            *v += 1;
-            *v
-        }
-        None => {
-            visits.insert(current, 1);
-            1
-        }
-    };
-
-    if let Cave::Small(_) = current {
-        if current_visits > 1 {
-            can_double_visit = false;
-        }
+    fn insert_edge(&mut self, a: &str, b: &str) {
+        let (a, b) = (self.cave(a), self.cave(b));
+        self.graph.add_undirected_edge(a, b, 1);
     }
 
-    if let Some(caves) = cave_system.adjacency_list.get(current) {
-        for cave in caves.iter().filter(|c| *c != &Cave::Start) {
-            let can_visit_small = *visits.get(cave).unwrap_or(&0) >= 1 && !can_double_visit;
-            match (cave, can_visit_small) {
-                (Cave::Small(_), true) => {}
-                (Cave::End, _) => all_paths.push(vec![cave.clone(), current.clone()]),
-                (Cave::Small(_) | Cave::Big(_), _) => {
-                    let mut paths = find_paths(cave, cave_system, &visits, can_double_visit);
-
-                    for path in paths.iter_mut() {
-                        path.push(current.clone())
-                    }
-
-                    all_paths.extend(paths);
-                }
-                _ => unreachable!(),
-            }
+    /// Number of paths from `current` to the end, visiting small caves at
+    /// most once, except for a single one visited twice if `can_revisit`
+    fn count_paths(&self, current: NodeId, visits: &mut [u32], can_revisit: bool) -> usize {
+        if current == self.end {
+            return 1;
         }
-    }
 
-    all_paths
-}
+        let mut paths = 0;
+        for cave in self.graph.neighbours(current) {
+            if cave == self.start {
+                continue;
+            }
 
-fn parse_input(input: impl AsRef<str>) -> CaveSystem {
-    let mut graph = CaveSystem::new();
+            let revisit = self.small[cave] && visits[cave] > 0;
+            if revisit && !can_revisit {
+                continue;
+            }
 
-    for line in input.as_ref().lines() {
-        let mut split = line.split('-');
-        let n1 = Cave::new(split.next().unwrap().to_owned());
-        let n2 = Cave::new(split.next().unwrap().to_owned());
+            visits[cave] += 1;
+            paths += self.count_paths(cave, visits, can_revisit && !revisit);
+            visits[cave] -= 1;
+        }
 
-        graph.insert_edge(&n1, &n2);
+        paths
     }
 
-    graph
-}
+    fn paths(&self, can_revisit: bool) -> usize {
+        let mut visits = vec![0; self.graph.len()];
+        visits[self.start] = 1;
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let cave_system = parse_input(input);
-
-    let paths: Vec<_> = find_paths(&Cave::Start, &cave_system, &HashMap::new(), false);
-
-    paths.len().to_string()
+        self.count_paths(self.start, &mut visits, can_revisit)
+    }
 }
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let cave_system = parse_input(input);
-
-    let paths: Vec<_> = find_paths(&Cave::Start, &cave_system, &HashMap::new(), true);
+impl Solver for Day12 {
+    type Input = CaveSystem;
 
-    paths.len().to_string()
-}
+    const DAY: u32 = 12;
+    const TITLE: &str = "Passage Pathing";
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+    fn parse(input: &str) -> Result<CaveSystem, ParseError> {
+        let source = Source::new(Self::DAY, input);
+        let mut graph = CaveSystem::new();
 
-    const DAY_NUM: u32 = 12;
+        for line in source.lines() {
+            let (a, b) = line
+                .split_once('-')
+                .ok_or_else(|| source.error(line, "expected `cave-cave`"))?;
 
-    const TEST_PART_1_RESULT: &'static str = "226";
-    const TEST_PART_2_RESULT: &'static str = "3509";
-    const REAL_PART_1_RESULT: &'static str = "3761";
-    const REAL_PART_2_RESULT: &'static str = "99138";
+            for name in [a, b] {
+                if name.is_empty() {
+                    return Err(source.error(name, "expected a cave name"));
+                }
+                source.check_chars(name, |c| c.is_ascii_alphabetic(), "expected a letter")?;
+            }
 
-    #[test]
-    fn test_part1() {
-        let result = part1(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_1_RESULT);
-        println!("Day {} Part 1 test result: {}", DAY_NUM, result);
-    }
+            graph.insert_edge(a, b);
+        }
 
-    #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_2_RESULT);
-        println!("Day {} Part 2 test result: {}", DAY_NUM, result);
+        Ok(graph)
     }
 
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_1_RESULT);
-        println!("Day {} Part 1 real result: {}", DAY_NUM, result);
+    fn part1(cave_system: &CaveSystem) -> Solution {
+        Ok(cave_system.paths(false).into())
     }
 
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_2_RESULT);
-        println!("Day {} Part 2 real result: {}", DAY_NUM, result);
+    fn part2(cave_system: &CaveSystem) -> Solution {
+        Ok(cave_system.paths(true).into())
     }
+}
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day12::solve_part1(input.as_ref())
+}
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
-    }
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day12::solve_part2(input.as_ref())
 }
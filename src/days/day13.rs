@@ -1,5 +1,13 @@
 // https://adventofcode.com/2021/day/13
 
+use super::{Param, Solver};
+use crate::{
+    geometry::{Bounds2, Point2},
+    Glyphs, Grid, NoSolution, ParseError, Solution, Source,
+};
+
+pub struct Day13;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum FoldAxis {
     X,
@@ -7,235 +15,150 @@ enum FoldAxis {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct FoldCommand {
+pub struct FoldCommand {
     coord: i32,
     axis: FoldAxis,
 }
 
-struct FoldablePaper {
-    points: Vec<bool>,
-    width: i32,
-    height: i32,
+#[derive(Debug, Clone)]
+pub struct FoldablePaper {
+    points: Grid<bool>,
 }
 
 impl FoldablePaper {
-    fn new(width: i32, height: i32) -> FoldablePaper {
-        FoldablePaper {
-            points: vec![false; (width * height).try_into().unwrap()],
-            width,
-            height,
-        }
+    fn count_marked(&self) -> usize {
+        self.points.iter().filter(|v| **v).count()
     }
 
-    fn mark_point(&mut self, x: i32, y: i32) {
-        let index: usize = (self.width * y + x).try_into().unwrap();
-        self.points[index] = true;
+    fn fold(&mut self, fold: &FoldCommand) {
+        let points = &self.points;
+        let (width, height) = (points.width(), points.height());
+
+        self.points = match fold.axis {
+            FoldAxis::X => Grid::from_fn(width / 2, height, |x, y| {
+                points[(x, y)] || points[(width - 1 - x, y)]
+            }),
+            FoldAxis::Y => Grid::from_fn(width, height / 2, |x, y| {
+                points[(x, y)] || points[(x, height - 1 - y)]
+            }),
+        };
     }
+}
 
-    fn is_marked(&self, x: i32, y: i32) -> bool {
-        let index: usize = (self.width * y + x).try_into().unwrap();
-        *self.points.get(index).unwrap()
-    }
+pub type Origami = (FoldablePaper, Vec<FoldCommand>);
 
-    fn count_marked(&self) -> i32 {
-        self.points
-            .iter()
-            .filter(|v| **v)
-            .count()
-            .try_into()
-            .unwrap()
-    }
+impl Solver for Day13 {
+    type Input = Origami;
 
-    fn fold(&mut self, fold: &FoldCommand) {
-        let (new_width, new_height) = match fold.axis {
-            FoldAxis::X => (self.width / 2, self.height),
-            FoldAxis::Y => (self.width, self.height / 2),
-        };
+    const DAY: u32 = 13;
+    const TITLE: &str = "Transparent Origami";
+    const PARAMS: &'static [Param] = &[Param {
+        name: "folds",
+        part: 1,
+        default: 1,
+    }];
 
-        let mut new_points = vec![false; (new_height * new_width) as usize];
-
-        match fold.axis {
-            FoldAxis::X => {
-                for y in 0..self.height {
-                    for x in 0..new_width {
-                        if self.is_marked(x, y) {
-                            new_points[(new_width * y + x) as usize] = true;
-                        }
-                        if self.is_marked(self.width - 1 - x, y) {
-                            new_points[(new_width * y + x) as usize] = true;
-                        }
-                    }
-                }
-            }
-            FoldAxis::Y => {
-                for y in 0..new_height {
-                    for x in 0..self.width {
-                        if self.is_marked(x, y) {
-                            new_points[(new_width * y + x) as usize] = true;
-                        }
-                        if self.is_marked(x, self.height - 1 - y) {
-                            new_points[(new_width * y + x) as usize] = true;
-                        }
-                    }
-                }
-            }
-        }
-
-        self.width = new_width;
-        self.height = new_height;
-        self.points = new_points;
-    }
-}
+    fn parse(input: &str) -> Result<Origami, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-fn parse_input(input: impl AsRef<str>) -> (FoldablePaper, Vec<FoldCommand>) {
-    let mut split = input.as_ref().split("\n\n");
+        let [dots, folds] = source.sections(input, ["dots", "fold instructions"])?;
 
-    let mut max_x = 0;
-    let mut max_y = 0;
+        let mut bounds = Bounds2::new(Point2::ORIGIN, Point2::ORIGIN);
 
-    let coords: Vec<_> = split
-        .next()
-        .unwrap()
-        .lines()
-        .map(|s| {
-            let mut line_split = s.split(',');
-            let x: i32 = line_split.next().unwrap().parse().unwrap();
-            let y: i32 = line_split.next().unwrap().parse().unwrap();
+        let dots = source.parse_lines(dots, |s| {
+            let [x, y] = source.exactly(s, source.numbers(s, ',')?, "`x,y`")?;
+            let dot = Point2::new(x, y);
 
-            max_x = max_x.max(x);
-            max_y = max_y.max(y);
+            if dot.x < 0 || dot.y < 0 {
+                return Err(source.error(s, "expected a non-negative point"));
+            }
+            bounds.include(dot);
 
-            (x, y)
-        })
-        .collect();
+            Ok(dot)
+        })?;
 
-    let mut foldable_paper = FoldablePaper::new(max_x + 1, max_y + 1);
+        let mut points = Grid::new(bounds.width() as usize, bounds.height() as usize, false);
 
-    for (x, y) in &coords {
-        foldable_paper.mark_point(*x, *y);
-    }
+        for dot in dots {
+            points[(dot.x as usize, dot.y as usize)] = true;
+        }
 
-    let commands: Vec<_> = split
-        .next()
-        .unwrap()
-        .lines()
-        .map(|s| {
-            let mut split = s.strip_prefix("fold along ").unwrap().split('=');
+        let commands = source.parse_lines(folds, |s| {
+            let instruction = source.prefix(s, "fold along ")?;
+            let (axis, coord) = source.split_pair(instruction, "=", "axis=coord")?;
 
-            let axis = match split.next().unwrap() {
+            let axis = match axis {
                 "x" => FoldAxis::X,
                 "y" => FoldAxis::Y,
-                _ => panic!("Unexpected fold axis"),
+                _ => return Err(source.error(axis, "expected fold axis `x` or `y`")),
             };
 
-            let coord = split.next().unwrap().parse().unwrap();
+            let coord = source.number(coord)?;
 
-            FoldCommand { coord, axis }
-        })
-        .collect();
+            Ok(FoldCommand { coord, axis })
+        })?;
 
-    (foldable_paper, commands)
-}
+        Ok((FoldablePaper { points }, commands))
+    }
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let (mut foldable_paper, fold_commands) = parse_input(input);
+    fn part1(origami: &Origami) -> Solution {
+        Self::run_with(origami, 1, &[1])
+    }
 
-    let cmd = &fold_commands[0];
-    foldable_paper.fold(cmd);
+    fn part2((foldable_paper, fold_commands): &Origami) -> Solution {
+        let mut foldable_paper = foldable_paper.clone();
 
-    foldable_paper.count_marked().to_string()
-}
+        for cmd in fold_commands {
+            foldable_paper.fold(cmd);
+        }
+
+        let points = &foldable_paper.points;
+        let mut glyphs = Glyphs::new(points.width(), points.height());
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let (mut foldable_paper, fold_commands) = parse_input(input);
+        for ((x, y), &marked) in points.enumerate() {
+            glyphs.set(x, y, marked);
+        }
 
-    for cmd in fold_commands {
-        foldable_paper.fold(&cmd);
+        Ok(glyphs.into())
     }
 
-    let mut s = String::new();
+    fn run_with(origami: &Origami, part: u32, values: &[usize]) -> Solution {
+        if part == 2 {
+            return Self::part2(origami);
+        }
 
-    for y in 0..foldable_paper.height {
-        for x in 0..foldable_paper.width {
-            if foldable_paper.is_marked(x, y) {
-                s.push('#');
-            } else {
-                s.push(' ');
-            }
+        let (foldable_paper, fold_commands) = origami;
+        let mut foldable_paper = foldable_paper.clone();
+
+        for cmd in fold_commands.get(..values[0]).ok_or(NoSolution)? {
+            foldable_paper.fold(cmd);
         }
-        s.push('\n');
+
+        Ok(foldable_paper.count_marked().into())
     }
+}
+
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day13::solve_part1(input.as_ref())
+}
 
-    s
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day13::solve_part2(input.as_ref())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
-
-    const DAY_NUM: u32 = 13;
-
-    const TEST_PART_1_RESULT: &'static str = "17";
-    const TEST_PART_2_RESULT: &'static str = "#####
-#   #
-#   #
-#   #
-#####
-     
-     
-";
-    const REAL_PART_1_RESULT: &'static str = "664";
-    const REAL_PART_2_RESULT: &'static str = "#### ####   ## #  # #### #    ###  #    
-#    #       # # #     # #    #  # #    
-###  ###     # ##     #  #    ###  #    
-#    #       # # #   #   #    #  # #    
-#    #    #  # # #  #    #    #  # #    
-#### #     ##  #  # #### #### ###  #### 
-";
 
     #[test]
-    fn test_part1_test() {
-        let result = part1(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_1_RESULT);
-        println!("Day {} Part 1 test result: {}", DAY_NUM, result);
-    }
-
-    #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_2_RESULT);
-        println!("Day {} Part 2 test result:\n{}", DAY_NUM, result);
-    }
-
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_1_RESULT);
-        println!("Day {} Part 1 real result: {}", DAY_NUM, result);
-    }
-
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_2_RESULT);
-        println!("Day {} Part 2 real result:\n{}", DAY_NUM, result);
-    }
-
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
-
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
+    fn test_parse_error() {
+        let error = part1("6,10\n0,14\n\nfold along z=7\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (4, 12, "z")
+        );
+
+        let error = part1("6,10\n0,14\n").unwrap_err();
+        assert_eq!(error.message, "expected fold instructions");
     }
 }
@@ -1,127 +1,103 @@
-use std::{collections::HashMap, hash::Hash};
-
 // https://adventofcode.com/2021/day/14
 
-fn parse_input(input: impl AsRef<str>) -> (Vec<char>, HashMap<(char, char), char>) {
-    let mut split = input.as_ref().split("\n\n");
-
-    let starting_poly = split.next().unwrap().chars().collect();
-
-    let mut chem_map = HashMap::new();
+use super::{Param, Solver};
+use crate::{NoSolution, ParseError, Solution, Source};
+use std::collections::HashMap;
 
-    for mapping in split.next().unwrap().lines() {
-        let mut mapping_split = mapping.split(" -> ");
+pub struct Day14;
 
-        let mut from_chems = mapping_split.next().unwrap().chars();
-        let from_chem_a = from_chems.next().unwrap();
-        let from_chem_b = from_chems.next().unwrap();
+pub type Polymer = (Vec<char>, HashMap<(char, char), char>);
 
-        let to_chem = mapping_split.next().unwrap().chars().next().unwrap();
+impl Solver for Day14 {
+    type Input = Polymer;
 
-        chem_map.insert((from_chem_a, from_chem_b), to_chem);
-    }
+    const DAY: u32 = 14;
+    const TITLE: &str = "Extended Polymerization";
+    const PARAMS: &'static [Param] = &[Param {
+        name: "steps",
+        part: 1,
+        default: 10,
+    }];
 
-    (starting_poly, chem_map)
-}
+    fn parse(input: &str) -> Result<Polymer, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let (starting_poly, chem_map) = parse_input(input);
+        let [template, rules] =
+            source.sections(input, ["a polymer template", "insertion rules"])?;
 
-    let mut current_poly = starting_poly;
+        let starting_poly = template.chars().collect();
 
-    for i in 0..40 {
-        println!("{}", i);
-        let mut next_poly = Vec::new();
+        let chem_map = source
+            .parse_lines(rules, |rule| {
+                let (pair, element) = source.split_pair(rule, " -> ", "AB -> C")?;
 
-        for c in current_poly.windows(2) {
-            next_poly.push(c[0]);
-            next_poly.push(chem_map[&(c[0], c[1])]);
-        }
+                let [a, b] = source.exactly(pair, pair.chars().collect(), "a pair of elements")?;
+                let [c] = source.exactly(element, element.chars().collect(), "a single element")?;
 
-        next_poly.push(*current_poly.last().unwrap());
+                Ok(((a, b), c))
+            })?
+            .into_iter()
+            .collect();
 
-        current_poly = next_poly;
+        Ok((starting_poly, chem_map))
     }
 
-    let mut quantities = HashMap::new();
-
-    for c in current_poly {
-        match quantities.get_mut(&c) {
-            Some(v) => *v += 1,
-            None => {
-                quantities.insert(c, 1);
-            }
-        }
+    fn part1(polymer: &Polymer) -> Solution {
+        Self::run_with(polymer, 1, &[10])
     }
 
-    let mut sorted_quantites = quantities.iter().collect::<Vec<_>>();
-    sorted_quantites.sort_by(|(_, a), (_, b)| b.cmp(a));
+    /// Not solved yet
+    fn part2(_polymer: &Polymer) -> Solution {
+        Err(NoSolution)
+    }
 
-    let val = sorted_quantites.first().unwrap().1 - sorted_quantites.last().unwrap().1;
+    fn run_with(polymer: &Polymer, part: u32, values: &[usize]) -> Solution {
+        if part == 2 {
+            return Self::part2(polymer);
+        }
 
-    val.to_string()
-}
+        let (starting_poly, chem_map) = polymer;
+        let mut current_poly = starting_poly.clone();
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let parsed_input = parse_input(input);
+        for _ in 0..values[0] {
+            let mut next_poly = Vec::new();
 
-    String::new()
-}
+            for c in current_poly.windows(2) {
+                next_poly.push(c[0]);
+                if let Some(&inserted) = chem_map.get(&(c[0], c[1])) {
+                    next_poly.push(inserted);
+                }
+            }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+            next_poly.push(*current_poly.last().unwrap());
 
-    const DAY_NUM: u32 = 14;
+            current_poly = next_poly;
+        }
 
-    const TEST_PART_1_RESULT: &'static str = "1588";
-    const TEST_PART_2_RESULT: &'static str = "";
-    const REAL_PART_1_RESULT: &'static str = "2003";
-    const REAL_PART_2_RESULT: &'static str = "";
+        let mut quantities = HashMap::new();
 
-    #[test]
-    fn test_part1() {
-        let result = part1(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_1_RESULT);
-        println!("Day {} Part 1 test result: {}", DAY_NUM, result);
-    }
+        for c in current_poly {
+            match quantities.get_mut(&c) {
+                Some(v) => *v += 1,
+                None => {
+                    quantities.insert(c, 1);
+                }
+            }
+        }
 
-    #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_2_RESULT);
-        println!("Day {} Part 2 test result: {}", DAY_NUM, result);
-    }
+        let mut sorted_quantites = quantities.iter().collect::<Vec<_>>();
+        sorted_quantites.sort_by(|(_, a), (_, b)| b.cmp(a));
 
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_1_RESULT);
-        println!("Day {} Part 1 real result: {}", DAY_NUM, result);
-    }
+        let val = sorted_quantites.first().unwrap().1 - sorted_quantites.last().unwrap().1;
 
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_2_RESULT);
-        println!("Day {} Part 2 real result: {}", DAY_NUM, result);
+        Ok(val.into())
     }
+}
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day14::solve_part1(input.as_ref())
+}
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
-    }
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day14::solve_part2(input.as_ref())
 }
@@ -1,93 +1,138 @@
 // https://adventofcode.com/2021/day/2
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let mut depth = 0;
-    let mut hor_distance = 0;
+use super::Solver;
+use crate::{
+    stream::{self, Fold, StreamError},
+    ParseError, Solution, Source,
+};
+use std::io::BufRead;
+
+pub struct Day2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(i64),
+    Up(i64),
+    Down(i64),
+}
 
-    for line in input.as_ref().lines() {
-        let mut data = line.split(' ');
+fn parse_line<'a>(source: &Source<'a>, line: &'a str) -> Result<Command, ParseError> {
+    let mut data = line.split(' ');
 
-        let direction = data.next().unwrap();
-        let x = data.next().unwrap().parse::<i32>().unwrap();
+    let direction = source.expect(data.next(), line, "a direction")?;
+    let x = source.number(source.expect(data.next(), line, "an amount")?)?;
 
-        match direction {
-            "forward" => hor_distance += x,
-            "up" => depth -= x,
-            "down" => depth += x,
-            _ => panic!("Unknown direction: {}", direction),
-        }
+    match direction {
+        "forward" => Ok(Command::Forward(x)),
+        "up" => Ok(Command::Up(x)),
+        "down" => Ok(Command::Down(x)),
+        _ => Err(source.error(direction, "unknown direction")),
     }
+}
 
-    format!("{}", hor_distance * depth)
+/// Submarine moved by reading up and down as depth changes
+#[derive(Debug, Default)]
+pub struct Position {
+    hor_distance: i64,
+    depth: i64,
 }
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let mut aim = 0;
-    let mut hor_distance = 0;
-    let mut depth = 0;
+impl Fold<Command> for Position {
+    fn push(&mut self, command: &Command) {
+        match *command {
+            Command::Forward(x) => self.hor_distance += x,
+            Command::Up(x) => self.depth -= x,
+            Command::Down(x) => self.depth += x,
+        }
+    }
 
-    for line in input.as_ref().lines() {
-        let mut data = line.split(' ');
+    fn finish(self) -> Solution {
+        Ok((self.hor_distance * self.depth).into())
+    }
+}
 
-        let direction = data.next().unwrap();
-        let x = data.next().unwrap().parse::<i32>().unwrap();
+/// Submarine moved by reading up and down as aim changes
+#[derive(Debug, Default)]
+pub struct AimedPosition {
+    aim: i64,
+    hor_distance: i64,
+    depth: i64,
+}
 
-        match direction {
-            "forward" => {
-                hor_distance += x;
-                depth += x * aim;
+impl Fold<Command> for AimedPosition {
+    fn push(&mut self, command: &Command) {
+        match *command {
+            Command::Forward(x) => {
+                self.hor_distance += x;
+                self.depth += x * self.aim;
             }
-            "up" => aim -= x,
-            "down" => aim += x,
-            _ => panic!("Unknown direction: {}", direction),
+            Command::Up(x) => self.aim -= x,
+            Command::Down(x) => self.aim += x,
         }
     }
 
-    format!("{}", hor_distance * depth)
+    fn finish(self) -> Solution {
+        Ok((self.hor_distance * self.depth).into())
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+impl Solver for Day2 {
+    type Input = Vec<Command>;
 
-    const DAY_NUM: u32 = 2;
+    const DAY: u32 = 2;
+    const TITLE: &str = "Dive!";
 
-    use super::*;
+    fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(get_test(2)), "150");
+        source.parse_lines(input, |line| parse_line(&source, line))
     }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(get_test(2)), "900");
+    fn part1(commands: &Vec<Command>) -> Solution {
+        Position::fold(commands)
     }
 
-    #[test]
-    fn test_part1_real() {
-        assert_eq!(part1(get_real(2)), "1815044");
+    fn part2(commands: &Vec<Command>) -> Solution {
+        AimedPosition::fold(commands)
     }
+}
+
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day2::solve_part1(input.as_ref())
+}
+
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day2::solve_part2(input.as_ref())
+}
+
+/// Solve part 1 reading the input one line at a time
+pub fn part1_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    stream::fold_lines(Day2::DAY, reader, parse_line).map(Position::finish)
+}
+
+/// Solve part 2 reading the input one line at a time
+pub fn part2_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    stream::fold_lines(Day2::DAY, reader, parse_line).map(AimedPosition::finish)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn test_part2_real() {
-        assert_eq!(part2(get_real(2)), "1739283308");
-    }
+    fn test_parse_error() {
+        let error = part1("forward 5\nsideways 3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.text, "sideways");
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
+        let error = part1("forward 5\nup\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
+        match part2_reader("forward 5\n\nsideways 3\n".as_bytes()) {
+            Err(StreamError::Parse(error)) => {
+                assert_eq!(Err(error), part2("forward 5\n\nsideways 3\n"))
+            }
+            other => panic!("expected a parse error, found {:?}", other),
+        }
     }
 }
@@ -1,160 +1,227 @@
 // https://adventofcode.com/2021/day/3
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let bit_count = input.as_ref().lines().next().unwrap().len();
+use super::Solver;
+use crate::{
+    stream::{self, Fold, StreamError},
+    NoSolution, ParseError, Solution, Source,
+};
+use std::io::BufRead;
+
+pub struct Day3;
+
+/// Check a line of the report, `bit_count` being set by the first line
+fn parse_line<'a>(
+    source: &Source<'a>,
+    line: &'a str,
+    bit_count: &mut Option<usize>,
+) -> Result<String, ParseError> {
+    let bit_count = *bit_count.get_or_insert(line.len());
+
+    source.check_chars(line, |c| c == '0' || c == '1', "expected a binary digit")?;
+
+    if line.len() != bit_count {
+        return Err(source.error(line, format!("expected {} bits", bit_count)));
+    }
 
-    let mut zero_count = vec![0; bit_count];
-    let mut one_count = vec![0; bit_count];
+    Ok(line.to_owned())
+}
 
-    for line in input.as_ref().lines() {
-        for (i, bit) in line.chars().enumerate() {
-            let bit = bit.to_digit(10).unwrap();
-            if bit == 0 {
-                zero_count[i] += 1;
-            } else {
-                one_count[i] += 1;
+/// Number of ones in each position of the report
+#[derive(Debug, Default)]
+pub struct BitCounts {
+    ones: Vec<usize>,
+    lines: usize,
+}
+
+impl Fold<String> for BitCounts {
+    fn push(&mut self, line: &String) {
+        self.ones.resize(line.len(), 0);
+        for (count, bit) in self.ones.iter_mut().zip(line.bytes()) {
+            if bit == b'1' {
+                *count += 1;
             }
         }
+        self.lines += 1;
     }
 
-    let mut gamma = 0;
-    let mut epsilon = 0;
+    fn finish(self) -> Solution {
+        let mut gamma = 0u64;
+        let mut epsilon = 0u64;
+
+        for &ones in &self.ones {
+            gamma <<= 1;
+            epsilon <<= 1;
 
-    for i in 0..bit_count {
-        if one_count[bit_count - i - 1] > zero_count[bit_count - i - 1] {
-            gamma += 1 << i;
-        } else {
-            epsilon += 1 << i;
+            if ones > self.lines - ones {
+                gamma += 1;
+            } else {
+                epsilon += 1;
+            }
         }
-    }
 
-    format!("{}", gamma * epsilon)
+        Ok((gamma * epsilon).into())
+    }
 }
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let bit_count = input.as_ref().lines().next().unwrap().len();
-
-    // Find oxygen generator rating
-    let mut oxygen_ratings = input
-        .as_ref()
-        .lines()
-        .map(|s| s.to_owned())
-        .collect::<Vec<String>>();
+#[derive(Debug, Default, Clone, Copy)]
+struct Node {
+    /// Number of lines starting with the bits leading to this node
+    count: usize,
+    children: [Option<usize>; 2],
+}
 
-    for i in 0..bit_count {
-        let mut zero_count = 0;
-        let mut one_count = 0;
+/// Every line of the report stored once per distinct prefix, which is all
+/// the ratings need to filter it bit by bit
+#[derive(Debug)]
+pub struct ReportTrie {
+    nodes: Vec<Node>,
+}
 
-        for line in &oxygen_ratings {
-            if line.chars().nth(i).unwrap() == '0' {
-                zero_count += 1;
-            } else {
-                one_count += 1;
-            }
+impl Default for ReportTrie {
+    fn default() -> Self {
+        ReportTrie {
+            nodes: vec![Node::default()],
         }
+    }
+}
 
-        let most_common = if zero_count > one_count { '0' } else { '1' };
-
-        // Delete lines that don't have the most common bit
-        oxygen_ratings = oxygen_ratings
-            .into_iter()
-            .filter(|line| line.chars().nth(i).unwrap() == most_common)
-            .collect::<Vec<_>>();
+impl ReportTrie {
+    fn count(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |node| self.nodes[node].count)
     }
 
-    // Find co2 scrubber rating
-    let mut co2_ratings = input
-        .as_ref()
-        .lines()
-        .map(|s| s.to_owned())
-        .collect::<Vec<String>>();
+    /// Follow the bits chosen by `keep` from zero and one counts down to a
+    /// line, `None` if a bit is kept that no line has
+    fn rating(&self, keep: impl Fn(usize, usize) -> usize) -> Option<u64> {
+        let mut node = 0;
+        let mut rating = 0;
 
-    for i in 0..bit_count {
-        let mut zero_count = 0;
-        let mut one_count = 0;
+        while self.nodes[node].children != [None; 2] {
+            let [zero, one] = self.nodes[node].children;
+            let bit = keep(self.count(zero), self.count(one));
 
-        for line in &co2_ratings {
-            if line.chars().nth(i).unwrap() == '0' {
-                zero_count += 1;
-            } else {
-                one_count += 1;
-            }
+            node = self.nodes[node].children[bit]?;
+            rating = rating << 1 | bit as u64;
         }
 
-        let least_common = if zero_count > one_count { '1' } else { '0' };
+        Some(rating)
+    }
+}
 
-        if co2_ratings.len() == 1 {
-            break;
+impl Fold<String> for ReportTrie {
+    fn push(&mut self, line: &String) {
+        let mut node = 0;
+        self.nodes[0].count += 1;
+
+        for bit in line.bytes() {
+            let bit = usize::from(bit == b'1');
+            node = match self.nodes[node].children[bit] {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children[bit] = Some(self.nodes.len() - 1);
+                    self.nodes.len() - 1
+                }
+            };
+            self.nodes[node].count += 1;
         }
-
-        // Delete lines that don't have the least common bit
-        co2_ratings = co2_ratings
-            .into_iter()
-            .filter(|line| line.chars().nth(i).unwrap() == least_common)
-            .collect::<Vec<_>>();
     }
 
-    // Convert to bit string to int
-    let mut oxygen_rating = 0;
-    let mut co2_rating = 0;
+    fn finish(self) -> Solution {
+        // Most common bit, ones winning a tie
+        let oxygen = self.rating(|zeros, ones| usize::from(zeros <= ones));
 
-    for (j, bit) in oxygen_ratings[0].chars().enumerate() {
-        if bit == '1' {
-            oxygen_rating += 1 << (bit_count - j - 1);
-        }
-    }
+        // Least common bit, zeros winning a tie, until a single line is left
+        let co2 = self.rating(|zeros, ones| match (zeros, ones) {
+            (0, 1) => 1,
+            (1, 0) => 0,
+            _ => usize::from(zeros > ones),
+        });
 
-    for (j, bit) in co2_ratings[0].chars().enumerate() {
-        if bit == '1' {
-            co2_rating += 1 << (bit_count - j - 1);
+        match (oxygen, co2) {
+            (Some(oxygen), Some(co2)) => Ok((oxygen * co2).into()),
+            _ => Err(NoSolution),
         }
     }
-
-    format!("{}", oxygen_rating * co2_rating)
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+impl Solver for Day3 {
+    type Input = Vec<String>;
 
-    const DAY_NUM: u32 = 3;
+    const DAY: u32 = 3;
+    const TITLE: &str = "Binary Diagnostic";
 
-    use super::*;
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(get_test(3)), "198");
+        source.expect(source.lines().next(), input, "a line")?;
+
+        let mut bit_count = None;
+        source.parse_lines(input, |line| parse_line(&source, line, &mut bit_count))
     }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(get_test(3)), "230");
+    fn part1(report: &Vec<String>) -> Solution {
+        BitCounts::fold(report)
     }
 
-    #[test]
-    fn test_part1_real() {
-        assert_eq!(part1(get_real(3)), "749376");
+    fn part2(report: &Vec<String>) -> Solution {
+        ReportTrie::fold(report)
     }
+}
 
-    #[test]
-    fn test_part2_real() {
-        assert_eq!(part2(get_real(3)), "2372923");
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day3::solve_part1(input.as_ref())
+}
+
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day3::solve_part2(input.as_ref())
+}
+
+/// Fold the report read one line at a time, which like the parsed report
+/// must not be empty
+fn fold_reader<F: Fold<String>>(reader: impl BufRead) -> Result<F, StreamError> {
+    let mut bit_count = None;
+    let fold = stream::fold_lines(Day3::DAY, reader, |source, line| {
+        parse_line(source, line, &mut bit_count)
+    })?;
+
+    match bit_count {
+        Some(_) => Ok(fold),
+        None => Err(Source::new(Day3::DAY, "")
+            .error("", "expected a line")
+            .into()),
     }
+}
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
+/// Solve part 1 reading the input one line at a time
+pub fn part1_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    fold_reader(reader).map(BitCounts::finish)
+}
+
+/// Solve part 2 reading the input one line at a time
+pub fn part2_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    fold_reader(reader).map(ReportTrie::finish)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_report() {
+        let error = part1("").unwrap_err();
+        assert_eq!(error.message, "expected a line");
+
+        match part2_reader("".as_bytes()) {
+            Err(StreamError::Parse(e)) => assert_eq!(e, error),
+            other => panic!("expected a parse error, found {:?}", other),
+        }
     }
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
+    #[test]
+    fn test_missing_co2_bit() {
+        // Both lines start with a zero, so the least common first bit is a
+        // one that no line has
+        assert_eq!(part2("011\n011\n"), Ok(Err(NoSolution)));
     }
 }
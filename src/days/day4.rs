@@ -1,24 +1,29 @@
 // https://adventofcode.com/2021/day/4
 
+use super::Solver;
+use crate::{NoSolution, ParseError, Solution, Source};
+
+pub struct Day4;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct BingoBoard {
+pub struct BingoBoard {
     board: [u32; 25],
     checked: [bool; 25],
 }
 
 impl BingoBoard {
-    fn from_string(input: impl AsRef<str>) -> BingoBoard {
+    fn parse<'a>(source: &Source<'a>, block: &'a str) -> Result<BingoBoard, ParseError> {
         let mut board = [0; 25];
         let checked = [false; 25];
 
-        let mut i = 0;
-        for line in input.as_ref().lines() {
-            for num in line.split_whitespace() {
-                board[i] = num.parse().unwrap();
-                i += 1;
-            }
+        let rows: [&str; 5] = source.exactly(block, block.lines().collect(), "5 rows")?;
+
+        for (y, row) in rows.into_iter().enumerate() {
+            let numbers: [u32; 5] = source.exactly(row, source.number_fields(row)?, "5 numbers")?;
+            board[y * 5..y * 5 + 5].copy_from_slice(&numbers);
         }
-        BingoBoard { board, checked }
+
+        Ok(BingoBoard { board, checked })
     }
 
     fn mark(&mut self, num: u32) {
@@ -70,123 +75,121 @@ impl BingoBoard {
     }
 }
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let draw_numbers = input
-        .as_ref()
-        .lines()
-        .next()
-        .unwrap()
-        .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<u32>>();
-
-    let boards_input: Vec<_> = input.as_ref().lines().skip(2).collect();
-    let mut boards: Vec<_> = boards_input
-        .chunks(6)
-        .into_iter()
-        .map(|chunck| {
-            let b_str = chunck.join("\n");
-            BingoBoard::from_string(&b_str)
-        })
-        .collect();
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bingo {
+    draw_numbers: Vec<u32>,
+    boards: Vec<BingoBoard>,
+}
+
+impl Solver for Day4 {
+    type Input = Bingo;
+
+    const DAY: u32 = 4;
+    const TITLE: &str = "Giant Squid";
 
-    for num in draw_numbers {
-        // Mark boards
-        for board in &mut boards {
-            board.mark(num);
+    fn parse(input: &str) -> Result<Bingo, ParseError> {
+        let source = Source::new(Self::DAY, input);
+
+        let blocks = source.blocks(input);
+        let (draws, boards) = blocks
+            .split_first()
+            .ok_or_else(|| source.error_after(input, "expected draw numbers"))?;
+
+        if let Some(separator) = draws.lines().nth(1) {
+            return Err(source.error(separator, "expected a blank line"));
         }
+        let draw_numbers = source.numbers(draws, ',')?;
+
+        let boards = boards
+            .iter()
+            .map(|board| BingoBoard::parse(&source, board))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Bingo {
+            draw_numbers,
+            boards,
+        })
+    }
+
+    fn part1(bingo: &Bingo) -> Solution {
+        let mut boards = bingo.boards.clone();
+
+        for &num in &bingo.draw_numbers {
+            // Mark boards
+            for board in &mut boards {
+                board.mark(num);
+            }
 
-        // Check if any board is bingo
-        for board in &boards {
-            if board.check_bingo() {
-                return (board.sum_unchecked() * num).to_string();
+            // Check if any board is bingo
+            for board in &boards {
+                if board.check_bingo() {
+                    return Ok((board.sum_unchecked() * num).into());
+                }
             }
         }
+
+        Err(NoSolution)
     }
 
-    String::from("No winning board found")
-}
+    fn part2(bingo: &Bingo) -> Solution {
+        let mut boards = bingo.boards.clone();
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let draw_numbers = input
-        .as_ref()
-        .lines()
-        .next()
-        .unwrap()
-        .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<u32>>();
-
-    let boards_input: Vec<_> = input.as_ref().lines().skip(2).collect();
-    let mut boards: Vec<_> = boards_input
-        .chunks(6)
-        .into_iter()
-        .map(|chunck| {
-            let b_str = chunck.join("\n");
-            BingoBoard::from_string(&b_str)
-        })
-        .collect();
+        for &num in &bingo.draw_numbers {
+            // Mark boards
+            for board in &mut boards {
+                board.mark(num);
+            }
 
-    for num in draw_numbers {
-        // Mark boards
-        for board in &mut boards {
-            board.mark(num);
-        }
+            // Check if a single board remains
+            if boards.len() == 1 && boards[0].check_bingo() {
+                return Ok((boards[0].sum_unchecked() * num).into());
+            }
 
-        // Check if a single board remains
-        if boards.len() == 1 && boards[0].check_bingo() {
-            return (boards[0].sum_unchecked() * num).to_string();
+            // Remove board if it is bingo
+            boards.retain(|board| !board.check_bingo());
         }
 
-        // Remove board if it is bingo
-        boards.retain(|board| !board.check_bingo());
+        Err(NoSolution)
     }
+}
 
-    String::from("No winning board found")
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day4::solve_part1(input.as_ref())
+}
+
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day4::solve_part2(input.as_ref())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+    use crate::get_test;
 
     const DAY_NUM: u32 = 4;
 
     use super::*;
 
     #[test]
-    fn test_part1() {
-        assert_eq!(part1(get_test(4)), "4512");
-    }
+    fn test_parse_error() {
+        let board = "1 2 3 4 5\n".repeat(5);
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(get_test(4)), "1924");
-    }
+        let error = part1(format!("7,4\n\n{}\n{}1 2 3 4\n", board, board)).unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (9, "expected 5 rows"));
 
-    #[test]
-    fn test_part1_real() {
-        assert_eq!(part1(get_real(4)), "4662");
+        let error = part1(format!("7,4\n{}", board)).unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected a blank line")
+        );
     }
 
     #[test]
-    fn test_part2_real() {
-        assert_eq!(part2(get_real(4)), "12080");
-    }
-
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
+    fn test_no_winning_board() {
+        let test_input = get_test(DAY_NUM).unwrap();
+        let boards = test_input.split_once('\n').unwrap().1;
+        let input = format!("99,98\n{}", boards);
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
+        assert_eq!(part1(&input), Ok(Err(NoSolution)));
+        assert_eq!(part2(&input), Ok(Err(NoSolution)));
     }
 }
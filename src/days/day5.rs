@@ -1,295 +1,153 @@
 // https://adventofcode.com/2021/day/5
 
-use core::cmp::Ordering;
-use std::{cmp, fmt, panic};
+use super::Solver;
+use crate::{
+    geometry::{Bounds2, Point2, Segment},
+    Grid, ParseError, Solution, Source,
+};
+use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl cmp::PartialOrd for Point {
-    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        match self.x.partial_cmp(&other.x) {
-            Some(Ordering::Equal) => {}
-            ord => return ord,
-        }
-        self.y.partial_cmp(&other.y)
-    }
-}
+pub struct Day5;
 
-impl cmp::Ord for Point {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.partial_cmp(other).unwrap()
-    }
+#[derive(Debug, Clone)]
+struct SeaBed {
+    grid: Grid<u32>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum LineType {
-    Horizontal,
-    Vertical,
-    // 45 degree forwards
-    ForwardDiagonal,
-    // 45 degree backwards
-    BackwardDiagonal,
-}
+impl fmt::Display for SeaBed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let chars = self.grid.map(|&n| match n {
+            0 => '.',
+            n => char::from_digit(n, 16).unwrap_or('+'),
+        });
 
-#[derive(Debug, Clone, Copy)]
-struct Line {
-    start: Point,
-    end: Point,
+        write!(f, "{}", chars)
+    }
 }
 
-impl Line {
-    fn new(start: Point, end: Point) -> Line {
-        Line { start, end }
+impl SeaBed {
+    fn new(width: usize, height: usize) -> SeaBed {
+        SeaBed {
+            grid: Grid::new(width, height, 0),
+        }
     }
 
-    fn line_type(&self) -> LineType {
-        let left_point = self.start.min(self.end);
-        let right_point = self.start.max(self.end);
+    fn plot(&mut self, point: Point2) {
+        self.grid[(point.x as usize, point.y as usize)] += 1;
+    }
 
-        match (left_point.x, left_point.y, right_point.x, right_point.y) {
-            (x, _, x2, _) if x == x2 => LineType::Vertical,
-            (_, y, _, y2) if y == y2 => LineType::Horizontal,
-            (x, y, x2, y2) if x < x2 && y < y2 => LineType::ForwardDiagonal,
-            (x, y, x2, y2) if x < x2 && y > y2 => LineType::BackwardDiagonal,
-            _ => panic!("unexpected line type"),
+    fn plot_line(&mut self, line: &Segment) {
+        for point in line.points() {
+            self.plot(point);
         }
     }
+
+    fn count_danger(&self) -> usize {
+        self.grid.iter().filter(|&c| *c >= 2).count()
+    }
 }
 
 #[derive(Debug, Clone)]
-struct SeaBed {
+pub struct Vents {
+    lines: Vec<Segment>,
     width: usize,
     height: usize,
-    grid: Vec<i32>,
-}
-
-impl fmt::Display for SeaBed {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let i = y * self.width + x;
-                let c = match self.grid[i] {
-                    0 => '.',
-                    n => char::from_digit(n as u32, 16).unwrap_or('+'),
-                };
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
-    }
 }
 
-impl SeaBed {
-    fn new(width: usize, height: usize) -> SeaBed {
-        SeaBed {
-            width,
-            height,
-            grid: vec![0; width * height],
-        }
-    }
+impl Solver for Day5 {
+    type Input = Vents;
 
-    fn plot(&mut self, point: &Point) {
-        let index = point.y * self.width as i32 + point.x;
-        self.grid[index as usize] += 1;
-    }
+    const DAY: u32 = 5;
+    const TITLE: &str = "Hydrothermal Venture";
 
-    fn plot_horizontal_vertical(&mut self, line: &Line) {
-        // Only concider horizontal or vertical lines
-        let line_type = line.line_type();
+    fn parse(input: &str) -> Result<Vents, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-        // horizontal line
-        if line_type == LineType::Horizontal {
-            let y = line.start.y;
-            let x_start = line.start.x.min(line.end.x);
-            let x_end = line.start.x.max(line.end.x);
+        let parse_point = |coord_pair| -> Result<Point2, ParseError> {
+            let [x, y] = source.exactly(coord_pair, source.numbers(coord_pair, ',')?, "`x,y`")?;
+            let point = Point2::new(x, y);
 
-            for x in x_start..=x_end {
-                self.plot(&Point { x, y });
+            if point.x < 0 || point.y < 0 {
+                return Err(source.error(coord_pair, "expected a non-negative point"));
             }
-        }
 
-        // vertical line
-        if line_type == LineType::Vertical {
-            let x = line.start.x;
-            let y_start = line.start.y.min(line.end.y);
-            let y_end = line.start.y.max(line.end.y);
+            Ok(point)
+        };
 
-            for y in y_start..=y_end {
-                self.plot(&Point { x, y });
+        let lines = source.parse_lines(input, |line| {
+            let (a, b) = source.split_pair(line, " -> ", "x1,y1 -> x2,y2")?;
+
+            let point_a = parse_point(a)?;
+            let point_b = parse_point(b)?;
+
+            let segment = Segment::new(point_a, point_b);
+            if point_a == point_b {
+                return Err(source.error(line, "expected a line between two points"));
+            }
+            if !(segment.is_horizontal() || segment.is_vertical() || segment.is_diagonal()) {
+                return Err(source.error(line, "expected a horizontal, vertical or 45 degree line"));
             }
+
+            Ok(segment)
+        })?;
+
+        let mut bounds = Bounds2::new(Point2::ORIGIN, Point2::ORIGIN);
+        for line in &lines {
+            bounds.include(line.start);
+            bounds.include(line.end);
         }
+
+        Ok(Vents {
+            lines,
+            width: bounds.width().try_into().unwrap(),
+            height: bounds.height().try_into().unwrap(),
+        })
     }
 
-    fn plot_line(&mut self, line: &Line) {
-        let line_type = line.line_type();
-
-        match line_type {
-            LineType::Horizontal | LineType::Vertical => self.plot_horizontal_vertical(line),
-            LineType::ForwardDiagonal | LineType::BackwardDiagonal => {
-                let left_point = line.start.min(line.end);
-                let right_point = line.start.max(line.end);
-
-                let dx = right_point.x - left_point.x;
-
-                for i in 0..=dx {
-                    let x = left_point.x + i;
-                    let y = if line_type == LineType::ForwardDiagonal {
-                        left_point.y + i
-                    } else {
-                        left_point.y - i
-                    };
-                    self.plot(&Point { x, y });
-                }
+    fn part1(vents: &Vents) -> Solution {
+        let mut sea_bed = SeaBed::new(vents.width, vents.height);
+
+        for line in &vents.lines {
+            if line.is_horizontal() || line.is_vertical() {
+                sea_bed.plot_line(line);
             }
         }
-    }
 
-    fn count_danger(&self) -> usize {
-        self.grid.iter().filter(|&c| *c >= 2).count()
+        Ok(sea_bed.count_danger().into())
     }
-}
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let mut max_x = 0;
-    let mut max_y = 0;
-
-    let lines: Vec<_> = input
-        .as_ref()
-        .lines()
-        .map(|line| {
-            let mut points = line.split(" -> ").map(|coord_pair| {
-                let mut coord_pair_iterator =
-                    coord_pair.split(',').map(|s| s.parse::<i32>().unwrap());
-
-                let point = Point {
-                    x: coord_pair_iterator.next().unwrap(),
-                    y: coord_pair_iterator.next().unwrap(),
-                };
-
-                max_x = max_x.max(point.x);
-                max_y = max_y.max(point.y);
-
-                point
-            });
-            let point_a = points.next().unwrap();
-            let point_b = points.next().unwrap();
-
-            match point_a.cmp(&point_b) {
-                Ordering::Less => Line::new(point_a, point_b),
-                Ordering::Greater => Line::new(point_b, point_a),
-                Ordering::Equal => panic!("Invalid line"),
-            }
-        })
-        .collect();
+    fn part2(vents: &Vents) -> Solution {
+        let mut sea_bed = SeaBed::new(vents.width, vents.height);
 
-    let mut sea_bed = SeaBed::new(
-        (max_x + 1).try_into().unwrap(),
-        (max_y + 1).try_into().unwrap(),
-    );
+        for line in &vents.lines {
+            sea_bed.plot_line(line);
+        }
 
-    for line in lines {
-        sea_bed.plot_horizontal_vertical(&line);
+        Ok(sea_bed.count_danger().into())
     }
-
-    sea_bed.count_danger().to_string()
 }
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let mut max_x = 0;
-    let mut max_y = 0;
-
-    let lines: Vec<_> = input
-        .as_ref()
-        .lines()
-        .map(|line| {
-            let mut points = line.split(" -> ").map(|coord_pair| {
-                let mut coord_pair_iterator =
-                    coord_pair.split(',').map(|s| s.parse::<i32>().unwrap());
-
-                let point = Point {
-                    x: coord_pair_iterator.next().unwrap(),
-                    y: coord_pair_iterator.next().unwrap(),
-                };
-
-                max_x = max_x.max(point.x);
-                max_y = max_y.max(point.y);
-
-                point
-            });
-            let point_a = points.next().unwrap();
-            let point_b = points.next().unwrap();
-
-            match point_a.cmp(&point_b) {
-                Ordering::Less => Line::new(point_a, point_b),
-                Ordering::Greater => Line::new(point_b, point_a),
-                Ordering::Equal => panic!("Invalid line"),
-            }
-        })
-        .collect();
-
-    let mut sea_bed = SeaBed::new(
-        (max_x + 1).try_into().unwrap(),
-        (max_y + 1).try_into().unwrap(),
-    );
-
-    for line in lines {
-        sea_bed.plot_line(&line);
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day5::solve_part1(input.as_ref())
+}
 
-    sea_bed.count_danger().to_string()
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day5::solve_part2(input.as_ref())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
-
-    const DAY_NUM: u32 = 5;
-
-    const TEST_PART_1_RESULT: &'static str = "5";
-    const TEST_PART_2_RESULT: &'static str = "12";
-    const REAL_PART_1_RESULT: &'static str = "6548";
-    const REAL_PART_2_RESULT: &'static str = "19663";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(get_test(5)), TEST_PART_1_RESULT);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(get_test(5)), TEST_PART_2_RESULT);
-    }
 
     #[test]
-    fn test_part1_real() {
-        assert_eq!(part1(get_real(5)), REAL_PART_1_RESULT);
-    }
+    fn test_parse_error() {
+        let error = part1("0,9 -> 5,9\n1,1 -> 1,1\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
 
-    #[test]
-    fn test_part2_real() {
-        assert_eq!(part2(get_real(5)), REAL_PART_2_RESULT);
-    }
+        let error = part1("0,9 -> 5,9\n1,x -> 1,3\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "x"));
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
-
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
+        let error = part1("0,0 -> 1,3\n").unwrap_err();
+        assert_eq!(error.line, 1);
     }
 }
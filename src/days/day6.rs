@@ -1,20 +1,15 @@
 // https://adventofcode.com/2021/day/6
 
-fn parse_input(input: impl AsRef<str>) -> Vec<usize> {
-    input
-        .as_ref()
-        .trim()
-        .split(',')
-        .map(|x| x.parse::<usize>().unwrap())
-        .collect()
-}
+use super::{Param, Solver};
+use crate::{ParseError, Solution, Source};
+
+pub struct Day6;
 
-fn comput_fish_count(input: impl AsRef<str>, day: usize) -> usize {
-    let parsed_input = parse_input(input);
+fn comput_fish_count(fish: &[usize], day: usize) -> usize {
     let mut fish_cohorts = [0usize; 9];
 
-    for i in parsed_input {
-        fish_cohorts[i as usize] += 1;
+    for &i in fish {
+        fish_cohorts[i] += 1;
     }
 
     for _ in 0..day {
@@ -25,68 +20,50 @@ fn comput_fish_count(input: impl AsRef<str>, day: usize) -> usize {
     fish_cohorts.iter().sum()
 }
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    comput_fish_count(input, 80).to_string()
-}
-
-pub fn part2(input: impl AsRef<str>) -> String {
-    comput_fish_count(input, 256).to_string()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
-
-    const DAY_NUM: u32 = 6;
-
-    const TEST_PART_1_RESULT: &'static str = "5934";
-    const TEST_PART_2_RESULT: &'static str = "26984457539";
-    const REAL_PART_1_RESULT: &'static str = "379414";
-    const REAL_PART_2_RESULT: &'static str = "1705008653296";
-
-    #[test]
-    fn test_part1() {
-        let result = part1(get_test(DAY_NUM));
-        println!("Part 1 test result: {}", result);
-        assert_eq!(result, TEST_PART_1_RESULT);
+impl Solver for Day6 {
+    type Input = Vec<usize>;
+
+    const DAY: u32 = 6;
+    const TITLE: &str = "Lanternfish";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "days",
+            part: 1,
+            default: 80,
+        },
+        Param {
+            name: "days",
+            part: 2,
+            default: 256,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        let source = Source::new(Self::DAY, input);
+
+        source.list(input, ',', |x| match source.number::<usize>(x)? {
+            timer @ 0..=8 => Ok(timer),
+            _ => Err(source.error(x, "expected a timer between 0 and 8")),
+        })
     }
 
-    #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        println!("Part 2 test result: {}", result);
-        assert_eq!(result, TEST_PART_2_RESULT);
+    fn part1(fish: &Vec<usize>) -> Solution {
+        Ok(comput_fish_count(fish, 80).into())
     }
 
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        println!("Part 1 real result: {}", result);
-        assert_eq!(result, REAL_PART_1_RESULT);
+    fn part2(fish: &Vec<usize>) -> Solution {
+        Ok(comput_fish_count(fish, 256).into())
     }
 
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        println!("Part 2 real result: {}", result);
-        assert_eq!(result, REAL_PART_2_RESULT);
+    fn run_with(fish: &Vec<usize>, _part: u32, values: &[usize]) -> Solution {
+        Ok(comput_fish_count(fish, values[0]).into())
     }
+}
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(|| {
-            black_box(part1(input.clone()));
-        });
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day6::solve_part1(input.as_ref())
+}
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(|| {
-            black_box(part2(input.clone()));
-        });
-    }
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day6::solve_part2(input.as_ref())
 }
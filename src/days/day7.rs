@@ -1,13 +1,9 @@
 // https://adventofcode.com/2021/day/7
 
-fn parse_input(input: impl AsRef<str>) -> Vec<i32> {
-    input
-        .as_ref()
-        .trim()
-        .split(',')
-        .map(|v| v.parse().unwrap())
-        .collect::<Vec<_>>()
-}
+use super::Solver;
+use crate::{ParseError, Solution, Source};
+
+pub struct Day7;
 
 fn find_range(vals: &[i32]) -> (i32, i32) {
     (*vals.iter().min().unwrap(), *vals.iter().max().unwrap())
@@ -38,72 +34,31 @@ fn min_fule_exp(vals: &[i32]) -> i32 {
         .unwrap()
 }
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let parsed_input = parse_input(input);
-
-    min_fule_linear(&parsed_input).to_string()
-}
-
-pub fn part2(input: impl AsRef<str>) -> String {
-    let parsed_input = parse_input(input);
-
-    min_fule_exp(&parsed_input).to_string()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
-
-    const DAY_NUM: u32 = 7;
+impl Solver for Day7 {
+    type Input = Vec<i32>;
 
-    const TEST_PART_1_RESULT: &'static str = "37";
-    const TEST_PART_2_RESULT: &'static str = "168";
-    const REAL_PART_1_RESULT: &'static str = "352254";
-    const REAL_PART_2_RESULT: &'static str = "99053143";
+    const DAY: u32 = 7;
+    const TITLE: &str = "The Treachery of Whales";
 
-    #[test]
-    fn test_part1() {
-        let result = part1(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_1_RESULT);
-        println!("Day {} Part 1 test result: {}", DAY_NUM, result);
-    }
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-    #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_2_RESULT);
-        println!("Day {} Part 2 test result: {}", DAY_NUM, result);
+        source.numbers(input, ',')
     }
 
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_1_RESULT);
-        println!("Day {} Part 1 real result: {}", DAY_NUM, result);
+    fn part1(crabs: &Vec<i32>) -> Solution {
+        Ok(min_fule_linear(crabs).into())
     }
 
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_2_RESULT);
-        println!("Day {} Part 2 real result: {}", DAY_NUM, result);
+    fn part2(crabs: &Vec<i32>) -> Solution {
+        Ok(min_fule_exp(crabs).into())
     }
+}
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day7::solve_part1(input.as_ref())
+}
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
-    }
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day7::solve_part2(input.as_ref())
 }
@@ -1,13 +1,20 @@
 // https://adventofcode.com/2021/day/8
 
-use std::{collections::VecDeque, ops};
+use super::Solver;
+use crate::{
+    stream::{self, Fold, StreamError},
+    Answer, NoSolution, ParseError, Solution, Source,
+};
+use std::{collections::VecDeque, io::BufRead, ops};
+
+pub struct Day8;
 
 fn char_to_index(c: char) -> usize {
     c as usize - 'a' as usize
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct SevenSegment(u8);
+pub struct SevenSegment(u8);
 
 impl SevenSegment {
     fn new(input: &str) -> SevenSegment {
@@ -41,139 +48,185 @@ impl ops::Not for SevenSegment {
     }
 }
 
-fn parse_input(
-    input: impl AsRef<str>,
-) -> VecDeque<(VecDeque<SevenSegment>, VecDeque<SevenSegment>)> {
-    let mut lines = VecDeque::new();
-    for line in input.as_ref().lines() {
-        let mut split = line
-            .split('|')
-            .map(|s| s.trim().split(' ').map(SevenSegment::new).collect());
-
-        let left = split.next().unwrap();
-        let right = split.next().unwrap();
+pub type Entry = (VecDeque<SevenSegment>, VecDeque<SevenSegment>);
 
-        lines.push_back((left, right));
+fn parse_segments<'a>(
+    source: &Source<'a>,
+    s: &'a str,
+    count: usize,
+) -> Result<VecDeque<SevenSegment>, ParseError> {
+    let patterns: Vec<_> = s.split_whitespace().collect();
+    if patterns.len() != count {
+        return Err(source.error(s, format!("expected {} patterns", count)));
     }
 
-    lines
+    patterns
+        .into_iter()
+        .map(|pattern| {
+            source.check_chars(pattern, |c| ('a'..='g').contains(&c), "expected a-g")?;
+            Ok(SevenSegment::new(pattern))
+        })
+        .collect()
 }
 
-pub fn part1(input: impl AsRef<str>) -> String {
-    let parsed_input = parse_input(input);
+fn parse_line<'a>(source: &Source<'a>, line: &'a str) -> Result<Entry, ParseError> {
+    let (left, right) = source.split_pair(line, "|", "patterns | output")?;
 
-    let sum: usize = parsed_input
-        .iter()
-        .map(|(_, out)| {
-            out.iter()
-                .filter(|a| [2, 3, 4, 7].contains(&a.count_lit()))
-                .count()
-        })
-        .sum();
-
-    sum.to_string()
+    Ok((
+        parse_segments(source, left, 10)?,
+        parse_segments(source, right, 4)?,
+    ))
 }
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let parsed_input = parse_input(input);
+/// Value shown on the display of an entry, working out which pattern is
+/// which digit from their overlaps
+///
+/// An entry whose patterns cannot all be told apart, or whose output shows a
+/// pattern matching no digit, has no solution.
+fn decode((in_segments, out_segments): &Entry) -> Result<usize, NoSolution> {
+    let mut in_segments = in_segments.clone();
+    let mut seven_segments: [Option<SevenSegment>; 10] = [None; 10];
+    // Patterns put back in a row, a whole pass of them meaning none can be
+    // told apart any more
+    let mut put_back = 0;
+
+    while let Some(segment) = in_segments.pop_front() {
+        let segment_count = segment.count_lit();
+
+        let one = seven_segments[1].map(|s| (s & segment).count_lit());
+        let four = seven_segments[4].map(|s| (s & segment).count_lit());
+
+        let digit = match (segment_count, one, four) {
+            (2, _, _) => 1,
+            (3, _, _) => 7,
+            (4, _, _) => 4,
+            (7, _, _) => 8,
+            (6, Some(2), Some(4)) => 9,
+            (6, Some(2), Some(3)) => 0,
+            (6, Some(1), Some(3)) => 6,
+            (5, Some(2), Some(3)) => 3,
+            (5, Some(1), Some(3)) => 5,
+            (5, Some(1), Some(2)) => 2,
+            (_, _, _) => {
+                put_back += 1;
+                if put_back > in_segments.len() {
+                    return Err(NoSolution);
+                }
+                in_segments.push_back(segment);
+                continue;
+            }
+        };
 
-    let mut sum = 0;
+        seven_segments[digit] = Some(segment);
+        put_back = 0;
+    }
 
-    for (mut in_segments, out_segments) in parsed_input {
-        let mut seven_segments: [Option<SevenSegment>; 10] = [None; 10];
+    out_segments.iter().try_fold(0, |number, segment| {
+        let digit = seven_segments
+            .iter()
+            .position(|known| known.as_ref() == Some(segment))
+            .ok_or(NoSolution)?;
+        Ok(number * 10 + digit)
+    })
+}
 
-        while let Some(segment) = in_segments.pop_front() {
-            let segment_count = segment.count_lit();
+/// Number of output digits drawn with a unique number of segments
+#[derive(Debug, Default)]
+pub struct EasyDigits(usize);
 
-            let one = seven_segments[1].map(|s| (s & segment).count_lit());
-            let four = seven_segments[4].map(|s| (s & segment).count_lit());
+impl Fold<Entry> for EasyDigits {
+    fn push(&mut self, (_, out): &Entry) {
+        self.0 += out
+            .iter()
+            .filter(|a| [2, 3, 4, 7].contains(&a.count_lit()))
+            .count();
+    }
 
-            match (segment_count, one, four) {
-                (2, _, _) => seven_segments[1] = Some(segment),
-                (3, _, _) => seven_segments[7] = Some(segment),
-                (4, _, _) => seven_segments[4] = Some(segment),
-                (7, _, _) => seven_segments[8] = Some(segment),
-                (6, Some(2), Some(4)) => seven_segments[9] = Some(segment),
-                (6, Some(2), Some(3)) => seven_segments[0] = Some(segment),
-                (6, Some(1), Some(3)) => seven_segments[6] = Some(segment),
-                (5, Some(2), Some(3)) => seven_segments[3] = Some(segment),
-                (5, Some(1), Some(3)) => seven_segments[5] = Some(segment),
-                (5, Some(1), Some(2)) => seven_segments[2] = Some(segment),
-                (_, _, _) => in_segments.push_back(segment),
-            }
-        }
+    fn finish(self) -> Solution {
+        Ok(self.0.into())
+    }
+}
 
-        let mut number = 0;
-        for segment in &out_segments {
-            for (i, known_segment) in seven_segments.iter().enumerate() {
-                if let Some(inner_segment) = known_segment {
-                    if segment == inner_segment {
-                        number = number * 10 + i;
-                    }
-                }
-            }
-        }
+/// Sum of the decoded output values, `None` once an entry cannot be decoded
+#[derive(Debug)]
+pub struct OutputSum(Option<usize>);
 
-        sum += number;
+impl Default for OutputSum {
+    fn default() -> OutputSum {
+        OutputSum(Some(0))
+    }
+}
+
+impl Fold<Entry> for OutputSum {
+    fn push(&mut self, entry: &Entry) {
+        self.0 = match (self.0, decode(entry)) {
+            (Some(sum), Ok(value)) => Some(sum + value),
+            _ => None,
+        };
     }
 
-    sum.to_string()
+    fn finish(self) -> Solution {
+        self.0.map(Answer::from).ok_or(NoSolution)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+impl Solver for Day8 {
+    type Input = VecDeque<Entry>;
 
-    const DAY_NUM: u32 = 8;
+    const DAY: u32 = 8;
+    const TITLE: &str = "Seven Segment Search";
 
-    const TEST_PART_1_RESULT: &'static str = "26";
-    const TEST_PART_2_RESULT: &'static str = "61229";
-    const REAL_PART_1_RESULT: &'static str = "440";
-    const REAL_PART_2_RESULT: &'static str = "1046281";
+    fn parse(input: &str) -> Result<VecDeque<Entry>, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-    #[test]
-    fn test_part1() {
-        let result = part1(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_1_RESULT);
-        println!("Day {} Part 1 test result: {}", DAY_NUM, result);
-    }
+        let lines = source.parse_lines(input, |line| parse_line(&source, line))?;
 
-    #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_2_RESULT);
-        println!("Day {} Part 2 test result: {}", DAY_NUM, result);
+        Ok(lines.into())
     }
 
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_1_RESULT);
-        println!("Day {} Part 1 real result: {}", DAY_NUM, result);
+    fn part1(entries: &VecDeque<Entry>) -> Solution {
+        EasyDigits::fold(entries)
     }
 
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_2_RESULT);
-        println!("Day {} Part 2 real result: {}", DAY_NUM, result);
+    fn part2(entries: &VecDeque<Entry>) -> Solution {
+        OutputSum::fold(entries)
     }
+}
 
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day8::solve_part1(input.as_ref())
+}
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day8::solve_part2(input.as_ref())
+}
+
+/// Solve part 1 reading the input one line at a time
+pub fn part1_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    stream::fold_lines(Day8::DAY, reader, parse_line).map(EasyDigits::finish)
+}
+
+/// Solve part 2 reading the input one line at a time
+pub fn part2_reader(reader: impl BufRead) -> Result<Solution, StreamError> {
+    stream::fold_lines(Day8::DAY, reader, parse_line).map(OutputSum::finish)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undecodable_entry() {
+        // No pattern lights two segments, so 1 is never found and the five
+        // and six segment patterns cannot be told apart
+        let entry = "abc bcdef acdfg abcdf dab cefabd cdfgeb eafb cagedb acdefg \
+                     | cdfeb fcadb cdfeb cdbaf\n";
+        assert_eq!(part2(entry), Ok(Err(NoSolution)));
+        assert_eq!(part2_reader(entry.as_bytes()).unwrap(), Err(NoSolution));
+
+        // Every pattern is known but the output shows none of them
+        let entry = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab \
+                     | cdfeb fcadb cdfeb cdbag\n";
+        assert_eq!(part2(entry), Ok(Err(NoSolution)));
     }
 }
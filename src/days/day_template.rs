@@ -1,76 +1,35 @@
 // https://adventofcode.com/2021/day/?
 
-fn parse_input(input: impl AsRef<str>) -> () {
-    ()
-}
-
-pub fn part1(input: impl AsRef<str>) -> String {
-    let parsed_input = parse_input(input);
+use super::Solver;
+use crate::{NoSolution, ParseError, Solution, Source};
 
-    String::new()
-}
+pub struct Day0;
 
-pub fn part2(input: impl AsRef<str>) -> String {
-    let parsed_input = parse_input(input);
+impl Solver for Day0 {
+    type Input = Vec<String>;
 
-    String::new()
-}
+    const DAY: u32 = 0;
+    const TITLE: &str = "";
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{get_real, get_test};
-    use test::{black_box, Bencher};
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        let source = Source::new(Self::DAY, input);
 
-    const DAY_NUM: u32 = 0;
-
-    const TEST_PART_1_RESULT: &'static str = "";
-    const TEST_PART_2_RESULT: &'static str = "";
-    const REAL_PART_1_RESULT: &'static str = "";
-    const REAL_PART_2_RESULT: &'static str = "";
-
-    #[test]
-    fn test_part1() {
-        let result = part1(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_1_RESULT);
-        println!("Day {} Part 1 test result: {}", DAY_NUM, result);  
+        Ok(source.lines().map(str::to_string).collect())
     }
 
-    #[test]
-    fn test_part2() {
-        let result = part2(get_test(DAY_NUM));
-        assert_eq!(result, TEST_PART_2_RESULT);
-        println!("Day {} Part 2 test result: {}", DAY_NUM, result);  
+    fn part1(_lines: &Vec<String>) -> Solution {
+        Err(NoSolution)
     }
 
-    #[test]
-    fn test_part1_real() {
-        let result = part1(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_1_RESULT);
-        println!("Day {} Part 1 real result: {}", DAY_NUM, result);
-    }
-
-    #[test]
-    fn test_part2_real() {
-        let result = part2(get_real(DAY_NUM));
-        assert_eq!(result, REAL_PART_2_RESULT);
-        println!("Day {} Part 2 real result: {}", DAY_NUM, result);
-    }
-
-    #[bench]
-    fn part1_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part1(input.clone()));
-        });
+    fn part2(_lines: &Vec<String>) -> Solution {
+        Err(NoSolution)
     }
+}
 
-    #[bench]
-    fn part2_bench(b: &mut Bencher) {
-        let input = get_real(DAY_NUM);
-        b.iter(move || {
-            black_box(part2(input.clone()));
-        });
-    }
+pub fn part1(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day0::solve_part1(input.as_ref())
+}
 
+pub fn part2(input: impl AsRef<str>) -> Result<Solution, ParseError> {
+    Day0::solve_part2(input.as_ref())
 }
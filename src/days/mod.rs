@@ -3,6 +3,7 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 pub mod day13;
+pub mod day14;
 pub mod day2;
 pub mod day3;
 pub mod day4;
@@ -11,4 +12,405 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
-pub mod day14;
+
+use crate::{ParseError, Solution};
+use std::{any::Any, fmt};
+
+/// Number a part depends on, such as how many steps it simulates, which can
+/// be changed to explore the puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// Part that uses the parameter
+    pub part: u32,
+    /// Value the puzzle asks for
+    pub default: usize,
+}
+
+/// A single day's puzzle, split into a parse step and the two parts
+pub trait Solver {
+    /// Parsed puzzle input, shared by both parts
+    type Input: fmt::Debug + Send + Sync + 'static;
+
+    /// Day of the advent calendar
+    const DAY: u32;
+    /// Title of the puzzle
+    const TITLE: &'static str;
+    /// Parameters of the parts, none by default
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Solution;
+    fn part2(input: &Self::Input) -> Solution;
+
+    /// Solve a part with its parameters set to `values`, in the order they
+    /// are listed in `PARAMS`
+    ///
+    /// Given the defaults it solves the part exactly like `part1` or `part2`,
+    /// which is all it does for a day without parameters.
+    fn run_with(input: &Self::Input, part: u32, values: &[usize]) -> Solution {
+        let _ = values;
+        match part {
+            1 => Self::part1(input),
+            _ => Self::part2(input),
+        }
+    }
+
+    /// Parse the raw input and solve part 1
+    fn solve_part1(input: &str) -> Result<Solution, ParseError> {
+        Ok(Self::part1(&Self::parse(input)?))
+    }
+
+    /// Parse the raw input and solve part 2
+    fn solve_part2(input: &str) -> Result<Solution, ParseError> {
+        Ok(Self::part2(&Self::parse(input)?))
+    }
+}
+
+/// Type erased parsed input of one day, which can be solved repeatedly and
+/// shared by both parts
+pub struct Parsed {
+    day: u32,
+    input: Box<dyn Any + Send + Sync>,
+}
+
+impl Parsed {
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// The parsed input, `None` if it is not of type `T`
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.input.downcast_ref()
+    }
+}
+
+impl fmt::Debug for Parsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parsed")
+            .field("day", &self.day)
+            .finish_non_exhaustive()
+    }
+}
+
+fn parse_erased<S: Solver>(input: &str) -> Result<Parsed, ParseError> {
+    Ok(Parsed {
+        day: S::DAY,
+        input: Box::new(S::parse(input)?),
+    })
+}
+
+fn input_of<S: Solver>(parsed: &Parsed) -> &S::Input {
+    parsed.downcast_ref().unwrap_or_else(|| {
+        panic!(
+            "day {} cannot solve input parsed by day {}",
+            S::DAY,
+            parsed.day
+        )
+    })
+}
+
+fn part1_erased<S: Solver>(parsed: &Parsed) -> Solution {
+    S::part1(input_of::<S>(parsed))
+}
+
+fn part2_erased<S: Solver>(parsed: &Parsed) -> Solution {
+    S::part2(input_of::<S>(parsed))
+}
+
+fn run_with_erased<S: Solver>(parsed: &Parsed, part: u32, values: &[usize]) -> Solution {
+    S::run_with(input_of::<S>(parsed), part, values)
+}
+
+fn show_erased<S: Solver>(parsed: &Parsed) -> String {
+    format!("{:#?}", input_of::<S>(parsed))
+}
+
+/// Type erased entry in the day registry
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle {
+    pub day: u32,
+    pub title: &'static str,
+    pub params: &'static [Param],
+    parse: fn(&str) -> Result<Parsed, ParseError>,
+    part1: fn(&Parsed) -> Solution,
+    part2: fn(&Parsed) -> Solution,
+    run_with: fn(&Parsed, u32, &[usize]) -> Solution,
+    show: fn(&Parsed) -> String,
+}
+
+impl Puzzle {
+    const fn of<S: Solver>() -> Puzzle {
+        Puzzle {
+            day: S::DAY,
+            title: S::TITLE,
+            params: S::PARAMS,
+            parse: parse_erased::<S>,
+            part1: part1_erased::<S>,
+            part2: part2_erased::<S>,
+            run_with: run_with_erased::<S>,
+            show: show_erased::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Solve part 1 of an input parsed by this puzzle
+    ///
+    /// Panics if the input was parsed by another day.
+    pub fn part1(&self, parsed: &Parsed) -> Solution {
+        (self.part1)(parsed)
+    }
+
+    /// Solve part 2 of an input parsed by this puzzle
+    ///
+    /// Panics if the input was parsed by another day.
+    pub fn part2(&self, parsed: &Parsed) -> Solution {
+        (self.part2)(parsed)
+    }
+
+    /// Solve the given part of a parsed input, `None` if the part does not
+    /// exist
+    pub fn run(&self, part: u32, parsed: &Parsed) -> Option<Solution> {
+        match part {
+            1 => Some(self.part1(parsed)),
+            2 => Some(self.part2(parsed)),
+            _ => None,
+        }
+    }
+
+    /// Parameters of one part
+    pub fn part_params(&self, part: u32) -> impl Iterator<Item = &'static Param> {
+        self.params.iter().filter(move |param| param.part == part)
+    }
+
+    /// Solve the given part of a parsed input with its parameters set to
+    /// `values`, in the order of [`Puzzle::part_params`], `None` if the part
+    /// does not exist
+    ///
+    /// Panics if the input was parsed by another day or a value is missing.
+    pub fn run_with(&self, part: u32, parsed: &Parsed, values: &[usize]) -> Option<Solution> {
+        if !matches!(part, 1 | 2) {
+            return None;
+        }
+        assert_eq!(
+            values.len(),
+            self.part_params(part).count(),
+            "day {} part {} takes a value for each of its parameters",
+            self.day,
+            part
+        );
+
+        Some((self.run_with)(parsed, part, values))
+    }
+
+    /// The parsed input pretty printed with its `Debug` implementation
+    ///
+    /// Panics if the input was parsed by another day.
+    pub fn show(&self, parsed: &Parsed) -> String {
+        (self.show)(parsed)
+    }
+
+    /// Parse the raw input and solve the given part, `None` if the part does
+    /// not exist
+    pub fn solve(&self, part: u32, input: &str) -> Option<Result<Solution, ParseError>> {
+        if !matches!(part, 1 | 2) {
+            return None;
+        }
+
+        Some(
+            self.parse(input)
+                .map(|parsed| self.run(part, &parsed).unwrap()),
+        )
+    }
+}
+
+/// Every implemented day, sorted by day number
+pub static REGISTRY: &[Puzzle] = &[
+    Puzzle::of::<day1::Day1>(),
+    Puzzle::of::<day2::Day2>(),
+    Puzzle::of::<day3::Day3>(),
+    Puzzle::of::<day4::Day4>(),
+    Puzzle::of::<day5::Day5>(),
+    Puzzle::of::<day6::Day6>(),
+    Puzzle::of::<day7::Day7>(),
+    Puzzle::of::<day8::Day8>(),
+    Puzzle::of::<day9::Day9>(),
+    Puzzle::of::<day10::Day10>(),
+    Puzzle::of::<day11::Day11>(),
+    Puzzle::of::<day12::Day12>(),
+    Puzzle::of::<day13::Day13>(),
+    Puzzle::of::<day14::Day14>(),
+];
+
+/// Look up the puzzle for a day
+pub fn get(day: u32) -> Option<&'static Puzzle> {
+    REGISTRY.iter().find(|puzzle| puzzle.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::Outcome;
+
+    #[test]
+    fn registry_is_sorted_and_unique() {
+        for pair in REGISTRY.windows(2) {
+            assert!(pair[0].day < pair[1].day);
+        }
+    }
+
+    #[test]
+    fn registry_lookup() {
+        assert_eq!(get(1).unwrap().title, "Sonar Sweep");
+        assert_eq!(get(14).unwrap().day, 14);
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+    }
+
+    #[test]
+    fn registry_dispatch() {
+        let puzzle = get(1).unwrap();
+        let input = crate::get_test(1).unwrap();
+        assert_eq!(puzzle.solve(1, &input).unwrap(), day1::part1(&input));
+        assert_eq!(puzzle.solve(2, &input).unwrap(), day1::part2(&input));
+        assert!(puzzle.solve(3, &input).is_none());
+
+        let parsed = puzzle.parse(&input).unwrap();
+        assert_eq!(parsed.day(), 1);
+        assert_eq!(Ok(puzzle.part1(&parsed)), day1::part1(&input));
+        assert_eq!(Ok(puzzle.run(2, &parsed).unwrap()), day1::part2(&input));
+        assert!(puzzle.run(3, &parsed).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "day 2 cannot solve input parsed by day 1")]
+    fn registry_rejects_other_days_input() {
+        let parsed = get(1).unwrap().parse("1\n2\n").unwrap();
+        let _ = get(2).unwrap().part1(&parsed);
+    }
+
+    /// Parameters left at their defaults solve each part as the puzzle asks
+    #[test]
+    fn params_default_to_the_puzzle() {
+        for puzzle in REGISTRY {
+            let parsed = puzzle.parse(&crate::get_test(puzzle.day).unwrap()).unwrap();
+
+            for part in [1, 2] {
+                let defaults: Vec<_> = puzzle.part_params(part).map(|p| p.default).collect();
+                assert_eq!(
+                    puzzle.run_with(part, &parsed, &defaults),
+                    puzzle.run(part, &parsed),
+                    "day {} part {}",
+                    puzzle.day,
+                    part
+                );
+            }
+        }
+
+        let day6 = get(6).unwrap();
+        let parsed = day6.parse("3,4,3,1,2\n").unwrap();
+        assert_eq!(day6.run_with(1, &parsed, &[18]), Some(Ok(26.into())));
+        assert!(day6.show(&parsed).starts_with("[\n    3,\n    4,"));
+    }
+
+    /// Solve every example in the data directory and compare against its
+    /// expected answers
+    #[test]
+    fn examples() {
+        let mut failures = vec![];
+
+        for puzzle in REGISTRY {
+            let examples = crate::examples(puzzle.day).unwrap();
+            assert!(!examples.is_empty(), "day {} has no examples", puzzle.day);
+
+            for check in crate::verify::check_examples(puzzle, &examples) {
+                if let Outcome::Fail { expected, found } = check.outcome {
+                    failures.push(format!(
+                        "day {} part {} example {}: expected {:?}, found {:?}",
+                        puzzle.day, check.part, check.name, expected, found
+                    ));
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    /// Streaming days give the same answers and errors reading any input one
+    /// line at a time as they do from the whole string
+    #[test]
+    fn streaming_matches_strings() {
+        type Reader = fn(&[u8]) -> Result<Solution, crate::stream::StreamError>;
+        type Whole = fn(&str) -> Result<Solution, ParseError>;
+
+        macro_rules! parts {
+            ($day:ident) => {
+                [
+                    (
+                        |input| $day::part1(input),
+                        |input| $day::part1_reader(input),
+                    ),
+                    (
+                        |input| $day::part2(input),
+                        |input| $day::part2_reader(input),
+                    ),
+                ]
+            };
+        }
+
+        let days: [(u32, [(Whole, Reader); 2]); 5] = [
+            (1, parts!(day1)),
+            (2, parts!(day2)),
+            (3, parts!(day3)),
+            (8, parts!(day8)),
+            (10, parts!(day10)),
+        ];
+
+        for (day, parts) in days {
+            let mut inputs: Vec<String> = crate::examples(day)
+                .unwrap()
+                .into_iter()
+                .map(|example| example.input)
+                .collect();
+            inputs.extend(crate::get_real(day));
+            inputs.push(format!("{}\r\n#\n", inputs[0].trim_end()));
+
+            for input in &inputs {
+                for (part, (whole, reader)) in parts.iter().enumerate() {
+                    let streamed = match reader(input.as_bytes()) {
+                        Ok(solution) => Ok(solution),
+                        Err(crate::stream::StreamError::Parse(e)) => Err(e),
+                        Err(e) => panic!("day {} part {}: {}", day, part + 1, e),
+                    };
+                    assert_eq!(streamed, whole(input), "day {} part {}", day, part + 1);
+                }
+            }
+        }
+    }
+
+    /// Solve every real input and compare against the answers manifest,
+    /// pending answers are only reported
+    #[test]
+    fn answers() {
+        let manifest = crate::verify::Manifest::load().unwrap();
+        let mut failures = vec![];
+
+        for check in crate::verify::verify(&manifest, REGISTRY) {
+            match check.outcome {
+                Outcome::Pass => {}
+                Outcome::Pending(_) => {
+                    println!("day {} part {}: {}", check.day, check.part, check.outcome)
+                }
+                _ => failures.push(format!(
+                    "day {} part {}: {}",
+                    check.day, check.part, check.outcome
+                )),
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+}
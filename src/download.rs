@@ -0,0 +1,256 @@
+use crate::{
+    http::{self, Throttle},
+    normalize, real_path, Config,
+};
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Puzzle website used when the config does not name another one
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Delay between requests used when the config does not set one
+pub const DEFAULT_REQUEST_DELAY: Duration = Duration::from_secs(5);
+
+/// Year of the puzzles
+pub const YEAR: u32 = 2021;
+
+/// File in the data directory recording the time of the last request, shared
+/// by every command that talks to the website
+pub const STAMP_FILE: &str = ".last_request";
+
+#[derive(Debug)]
+pub enum DownloadError {
+    /// No session token in the config
+    MissingSession,
+    /// The input is already on disk and would be overwritten
+    AlreadyCached(PathBuf),
+    /// The website answered with something other than success
+    Status(u16, String),
+    /// The response does not look like a puzzle input
+    NotInput(&'static str),
+    Io(io::Error),
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadError::MissingSession => write!(f, "no `session` set in the config file"),
+            DownloadError::AlreadyCached(path) => {
+                write!(f, "{} already exists, not overwriting it", path.display())
+            }
+            DownloadError::Status(status, message) => {
+                write!(f, "server answered {}: {}", status, message)
+            }
+            DownloadError::NotInput(reason) => write!(f, "not a puzzle input: {}", reason),
+            DownloadError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for DownloadError {}
+
+impl From<io::Error> for DownloadError {
+    fn from(e: io::Error) -> DownloadError {
+        DownloadError::Io(e)
+    }
+}
+
+/// Check that a response body is a puzzle input rather than an error page
+pub fn check_input(body: &str) -> Result<(), &'static str> {
+    let lower = body.trim_start().to_ascii_lowercase();
+
+    if lower.is_empty() {
+        Err("the response is empty")
+    } else if lower.contains("please log in") || lower.contains("puzzle inputs differ by user") {
+        Err("the session token was rejected, it may have expired")
+    } else if lower.contains("before it unlocks") {
+        Err("the puzzle has not unlocked yet")
+    } else if lower.starts_with('<') || lower.contains("<html") || lower.contains("<!doctype") {
+        Err("the response is an html page")
+    } else {
+        Ok(())
+    }
+}
+
+/// Fetches puzzle inputs from the website
+#[derive(Debug, Clone)]
+pub struct Downloader {
+    base_url: String,
+    session: String,
+    throttle: Throttle,
+}
+
+impl Downloader {
+    pub fn new(
+        base_url: impl Into<String>,
+        session: impl Into<String>,
+        throttle: Throttle,
+    ) -> Self {
+        Downloader {
+            base_url: base_url.into(),
+            session: session.into(),
+            throttle,
+        }
+    }
+
+    /// Build a downloader from the config, throttled through a stamp file in
+    /// the data directory
+    pub fn from_config(config: &Config) -> Result<Downloader, DownloadError> {
+        let session = config
+            .session
+            .clone()
+            .ok_or(DownloadError::MissingSession)?;
+        let base_url = config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        http::check_client(&base_url)?;
+        let delay = config.request_delay.unwrap_or(DEFAULT_REQUEST_DELAY);
+
+        let stamp = crate::data_dir()?.join(STAMP_FILE);
+
+        Ok(Downloader::new(
+            base_url,
+            session,
+            Throttle::new(stamp, delay),
+        ))
+    }
+
+    /// Request a day's input
+    pub fn fetch(&self, day: u32) -> Result<String, DownloadError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        let cookie = format!("session={}", self.session);
+
+        self.throttle.wait()?;
+        let response = http::request("GET", &url, &[("Cookie", &cookie)], None)?;
+
+        if response.status != 200 {
+            let message = response.body.lines().next().unwrap_or("").trim();
+            return Err(DownloadError::Status(response.status, message.to_string()));
+        }
+
+        check_input(&response.body).map_err(DownloadError::NotInput)?;
+
+        Ok(response.body)
+    }
+
+    /// Download a day's input to `path`, refusing to replace an existing input
+    pub fn download_to(&self, day: u32, path: &Path) -> Result<(), DownloadError> {
+        if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Err(DownloadError::AlreadyCached(path.to_path_buf()));
+        }
+
+        let input = self.fetch(day)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, normalize(&input))?;
+
+        Ok(())
+    }
+
+    /// Download a day's input into the real input folder
+    pub fn download(&self, day: u32) -> Result<PathBuf, DownloadError> {
+        let path = real_path(day)?;
+        self.download_to(day, &path)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test_server;
+    use std::{env, process};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("advent2021-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn downloader(url: &str, dir: &Path) -> Downloader {
+        Downloader::new(
+            url,
+            "abc123",
+            Throttle::new(dir.join(".last_request"), Duration::ZERO),
+        )
+    }
+
+    #[test]
+    fn download_input() {
+        let dir = temp_dir("download");
+        let server = test_server::serve(vec![(200, "1\r\n2\r\n3\r\n".to_string())]);
+
+        let path = dir.join("real/day1.txt");
+        downloader(&server.url, &dir).download_to(1, &path).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+
+        let received = server.received.lock().unwrap();
+        assert_eq!(received[0].method, "GET");
+        assert_eq!(received[0].path, "/2021/day/1/input");
+        assert_eq!(received[0].header("Cookie"), Some("session=abc123"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuse_to_overwrite() {
+        let dir = temp_dir("download-cached");
+        let path = dir.join("day1.txt");
+        fs::write(&path, "1\n").unwrap();
+
+        // No server is needed, the request is never made
+        let result = downloader("http://127.0.0.1:1", &dir).download_to(1, &path);
+        assert!(matches!(result, Err(DownloadError::AlreadyCached(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reject_error_pages() {
+        let dir = temp_dir("download-errors");
+        let server = test_server::serve(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                    .to_string(),
+            ),
+            (
+                200,
+                "<!DOCTYPE html>\n<html><body>Oops</body></html>\n".to_string(),
+            ),
+        ]);
+        let downloader = downloader(&server.url, &dir);
+        let path = dir.join("day1.txt");
+
+        let result = downloader.download_to(1, &path);
+        assert!(matches!(result, Err(DownloadError::Status(400, _))));
+
+        let result = downloader.download_to(1, &path);
+        assert!(matches!(result, Err(DownloadError::NotInput(_))));
+
+        assert!(!path.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn check_input_bodies() {
+        assert!(check_input("199\n200\n").is_ok());
+        assert!(check_input("").is_err());
+        assert!(
+            check_input("Please don't repeatedly request this endpoint before it unlocks!")
+                .is_err()
+        );
+        assert!(check_input("<html></html>").is_err());
+    }
+}
@@ -0,0 +1,430 @@
+use crate::{
+    alloc::{self, format_bytes, Allocs},
+    bench::{format_duration, Phase},
+    days::Puzzle,
+    verify::{manifest_form, Manifest, Outcome},
+};
+use std::{
+    any::Any,
+    fmt::Write,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Number of threads used when none is given, one per core
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Run every job on a pool of `threads` threads and return their results in
+/// the order of the jobs
+///
+/// A job that panics gives its panic message instead, without taking the pool
+/// or the other jobs down with it.
+pub fn run_jobs<T, F>(threads: usize, jobs: Vec<F>) -> Vec<Result<T, String>>
+where
+    T: Send,
+    F: FnOnce() -> T + Send,
+{
+    let count = jobs.len();
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let results = Mutex::new((0..count).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, count.max(1)) {
+            scope.spawn(|| loop {
+                // Taken on its own line so the queue is unlocked while the job
+                // runs
+                let next = queue.lock().unwrap().next();
+                let Some((i, job)) = next else {
+                    break;
+                };
+
+                let result = catch_panic(job);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every job was run"))
+        .collect()
+}
+
+/// Run `f`, turning a panic into its message
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(message) => message.clone(),
+            None => "panicked".to_string(),
+        },
+    }
+}
+
+/// One part of a day in a [`Summary`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRun {
+    pub part: u32,
+    /// The solution as it is written in the manifest, `None` when the part
+    /// did not run to the end
+    pub answer: Option<String>,
+    pub outcome: Outcome,
+    pub time: Duration,
+    /// What solving allocated, when allocations are counted
+    pub allocs: Option<Allocs>,
+}
+
+/// Parse and both parts of a day in a [`Summary`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRun {
+    pub day: u32,
+    pub parse: Duration,
+    /// What parsing allocated, when allocations are counted
+    pub parse_allocs: Option<Allocs>,
+    pub parts: Vec<PartRun>,
+    /// Time the day kept its thread busy, reading the input included
+    pub busy: Duration,
+}
+
+impl DayRun {
+    /// Both parts failing with the same error, nothing having been solved
+    fn failed(day: u32, error: String, busy: Duration) -> DayRun {
+        DayRun {
+            day,
+            parse: Duration::ZERO,
+            parse_allocs: None,
+            parts: [1, 2]
+                .map(|part| PartRun {
+                    part,
+                    answer: None,
+                    outcome: Outcome::Error(error.clone()),
+                    time: Duration::ZERO,
+                    allocs: None,
+                })
+                .to_vec(),
+            busy,
+        }
+    }
+}
+
+/// Parse one day's input and solve both parts, each step isolated from panics
+/// in the others
+fn run_day(
+    puzzle: &Puzzle,
+    manifest: &Manifest,
+    load: impl Fn(u32) -> Result<String, String>,
+) -> DayRun {
+    let start = Instant::now();
+
+    let input = match catch_panic(|| load(puzzle.day)) {
+        Ok(Ok(input)) => input,
+        Ok(Err(e)) => return DayRun::failed(puzzle.day, e, start.elapsed()),
+        Err(panic) => {
+            let error = format!("panicked reading the input: {}", panic);
+            return DayRun::failed(puzzle.day, error, start.elapsed());
+        }
+    };
+
+    let parse_start = Instant::now();
+    let parsed = catch_panic(|| alloc::measure(|| puzzle.parse(&input)));
+    let parse = parse_start.elapsed();
+    let (parsed, parse_allocs) = match parsed {
+        Ok((Ok(parsed), allocs)) => (parsed, allocs),
+        Ok((Err(e), _)) => return DayRun::failed(puzzle.day, e.to_string(), start.elapsed()),
+        Err(panic) => {
+            let error = format!("panicked parsing: {}", panic);
+            return DayRun::failed(puzzle.day, error, start.elapsed());
+        }
+    };
+
+    let parts = [1, 2]
+        .map(|part| {
+            let part_start = Instant::now();
+            let solution = catch_panic(|| alloc::measure(|| puzzle.run(part, &parsed).unwrap()));
+            let time = part_start.elapsed();
+
+            let allocs = solution.as_ref().ok().and_then(|&(_, allocs)| allocs);
+            let answer = solution
+                .as_ref()
+                .ok()
+                .map(|(solution, _)| manifest_form(solution));
+            let found = answer
+                .clone()
+                .ok_or_else(|| format!("panicked: {}", solution.unwrap_err()));
+
+            PartRun {
+                part,
+                answer,
+                outcome: manifest.check(puzzle.day, part, found),
+                time,
+                allocs,
+            }
+        })
+        .to_vec();
+
+    DayRun {
+        day: puzzle.day,
+        parse,
+        parse_allocs,
+        parts,
+        busy: start.elapsed(),
+    }
+}
+
+/// Result of running every day at once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    /// Every day, in the order they were given
+    pub days: Vec<DayRun>,
+    pub threads: usize,
+    /// Time from starting the first day to finishing the last one
+    pub wall: Duration,
+}
+
+impl Summary {
+    /// Time the days kept their threads busy, summed over every thread
+    ///
+    /// This is wall time measured on each thread rather than CPU time, so a
+    /// thread waiting on a busy core still counts.
+    pub fn busy(&self) -> Duration {
+        self.days.iter().map(|day| day.busy).sum()
+    }
+
+    /// Parts that did not match the manifest or did not run to the end
+    pub fn failures(&self) -> impl Iterator<Item = (u32, &PartRun)> {
+        self.days.iter().flat_map(|day| {
+            day.parts
+                .iter()
+                .filter(|part| matches!(part.outcome, Outcome::Fail { .. } | Outcome::Error(_)))
+                .map(move |part| (day.day, part))
+        })
+    }
+
+    /// Table of every day's parse and parts, with what went wrong and the
+    /// total times below it
+    ///
+    /// What each step allocated is shown next to its time when allocations
+    /// are counted.
+    pub fn table(&self) -> String {
+        let answer_width = self
+            .days
+            .iter()
+            .flat_map(|day| &day.parts)
+            .filter_map(|part| part.answer.as_ref())
+            .map(|answer| answer.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Answer".len());
+
+        let mut table = String::new();
+        let mut row = |day: &str, phase: &str, answer: &str, check: &str, time: &str, allocs| {
+            writeln!(
+                table,
+                "{:>3}  {:<5}  {:<answer_width$}  {:<7}  {:>9}{}",
+                day, phase, answer, check, time, allocs
+            )
+            .unwrap()
+        };
+        let allocs = |allocs: Option<Allocs>| match allocs {
+            _ if !alloc::ENABLED => String::new(),
+            Some(allocs) => alloc_columns(
+                &allocs.count.to_string(),
+                &format_bytes(allocs.bytes),
+                &format_bytes(allocs.peak),
+            ),
+            None => alloc_columns("-", "-", "-"),
+        };
+
+        let header = match alloc::ENABLED {
+            true => alloc_columns("Allocs", "Bytes", "Peak"),
+            false => String::new(),
+        };
+        row("Day", "Phase", "Answer", "Check", "Time", header);
+        for day in &self.days {
+            let number = day.day.to_string();
+            row(
+                &number,
+                "parse",
+                "",
+                "",
+                &format_duration(day.parse),
+                allocs(day.parse_allocs),
+            );
+
+            for part in &day.parts {
+                let check = match part.outcome {
+                    Outcome::Pass => "ok",
+                    Outcome::Fail { .. } => "FAILED",
+                    Outcome::Pending(_) => "pending",
+                    Outcome::Error(_) => "error",
+                };
+                row(
+                    &number,
+                    &Phase::part(part.part).unwrap().to_string(),
+                    part.answer.as_deref().unwrap_or("-"),
+                    check,
+                    &format_duration(part.time),
+                    allocs(part.allocs),
+                );
+            }
+        }
+
+        let mut failures = self.failures().peekable();
+        if failures.peek().is_some() {
+            table.push('\n');
+        }
+        for (day, part) in failures {
+            writeln!(table, "Day {} part {}: {}", day, part.part, part.outcome).unwrap();
+        }
+
+        writeln!(
+            table,
+            "\n{} day(s) on {} thread(s): {} wall time, {} total solve time",
+            self.days.len(),
+            self.threads,
+            format_duration(self.wall),
+            format_duration(self.busy())
+        )
+        .unwrap();
+
+        table
+    }
+}
+
+fn alloc_columns(count: &str, bytes: &str, peak: &str) -> String {
+    format!("  {:>7}  {:>9}  {:>9}", count, bytes, peak)
+}
+
+/// Run every puzzle on the input `load` gives for its day, on a pool of
+/// `threads` threads, and check both parts against the manifest
+///
+/// A day that fails to load, parse or solve, even by panicking, only fails its
+/// own parts.
+pub fn run_all(
+    puzzles: &[Puzzle],
+    manifest: &Manifest,
+    threads: usize,
+    load: impl Fn(u32) -> Result<String, String> + Sync,
+) -> Summary {
+    let start = Instant::now();
+
+    let jobs = puzzles
+        .iter()
+        .map(|puzzle| || run_day(puzzle, manifest, &load))
+        .collect();
+    let days = run_jobs(threads, jobs)
+        .into_iter()
+        .zip(puzzles)
+        .map(|(day, puzzle)| {
+            day.unwrap_or_else(|panic| DayRun::failed(puzzle.day, panic, Duration::ZERO))
+        })
+        .collect();
+
+    Summary {
+        days,
+        threads,
+        wall: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days::REGISTRY, get_real};
+
+    #[test]
+    fn pool_keeps_order_and_isolates_panics() {
+        let jobs: Vec<Box<dyn FnOnce() -> u32 + Send>> = (0..20)
+            .map(|i| -> Box<dyn FnOnce() -> u32 + Send> {
+                match i {
+                    7 => Box::new(|| panic!("job 7 is broken")),
+                    13 => Box::new(|| panic!("job {} is broken", 13)),
+                    _ => Box::new(move || i * 2),
+                }
+            })
+            .collect();
+
+        let results = run_jobs(4, jobs);
+        assert_eq!(results.len(), 20);
+        assert_eq!(results[7], Err("job 7 is broken".to_string()));
+        assert_eq!(results[13], Err("job 13 is broken".to_string()));
+        assert_eq!(results[19], Ok(38));
+
+        let empty: Vec<fn() -> u32> = vec![];
+        assert!(run_jobs(4, empty).is_empty());
+    }
+
+    #[test]
+    fn run_every_day() {
+        let manifest = Manifest::parse("1 1 1466\n1 2 1\n").unwrap();
+        let load = |day| get_real(day).map_err(|e| e.to_string());
+
+        let summary = run_all(&REGISTRY[..3], &manifest, 2, load);
+        let days: Vec<_> = summary.days.iter().map(|day| day.day).collect();
+        assert_eq!(days, [1, 2, 3]);
+        assert_eq!(summary.days[0].parts[0].outcome, Outcome::Pass);
+        assert_eq!(summary.days[0].parts[1].answer.as_deref(), Some("1491"));
+        assert!(matches!(
+            summary.days[0].parts[1].outcome,
+            Outcome::Fail { .. }
+        ));
+        assert_eq!(summary.failures().count(), 1);
+
+        // Threads only change how long it takes
+        let serial = run_all(&REGISTRY[..3], &manifest, 1, load);
+        let answers = |summary: &Summary| {
+            summary
+                .days
+                .iter()
+                .flat_map(|day| day.parts.iter().map(|part| part.answer.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(answers(&summary), answers(&serial));
+
+        let table = summary.table();
+        let row = |fields: [&str; 4]| {
+            table
+                .lines()
+                .any(|line| line.split_whitespace().take(4).eq(fields))
+        };
+        assert!(row(["1", "1", "1466", "ok"]));
+        assert!(row(["3", "2", "2372923", "pending"]));
+        assert!(table.contains("Day 1 part 2: FAILED, expected 1 but found 1491"));
+        assert!(table.contains("3 day(s) on 2 thread(s)"));
+    }
+
+    #[test]
+    fn broken_day_fails_alone() {
+        let load = |day| match day {
+            2 => panic!("no input for day 2"),
+            3 => Err("missing".to_string()),
+            _ => get_real(day).map_err(|e| e.to_string()),
+        };
+
+        let summary = run_all(&REGISTRY[..3], &Manifest::default(), 3, load);
+        assert!(matches!(
+            summary.days[0].parts[0].outcome,
+            Outcome::Pending(_)
+        ));
+        assert_eq!(
+            summary.days[1].parts[1].outcome,
+            Outcome::Error("panicked reading the input: no input for day 2".to_string())
+        );
+        assert_eq!(
+            summary.days[2].parts[0].outcome,
+            Outcome::Error("missing".to_string())
+        );
+        assert_eq!(summary.failures().count(), 4);
+    }
+}
@@ -0,0 +1,755 @@
+use crate::{
+    days::{day11, day3},
+    Glyphs, Grid, Rng,
+};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Write,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Random input generator of one day
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub day: u32,
+    /// What the size of an input counts
+    pub unit: &'static str,
+    /// Size close to that of the real input
+    pub default_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    /// Valid puzzle input of `size` units, always the same for a seed
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+
+    /// Where an input with this size and seed is written in the data dir
+    pub fn path(&self, data_dir: &Path, size: usize, seed: u64) -> PathBuf {
+        data_dir.join(format!(
+            "generated/day{}/size{}-seed{}.txt",
+            self.day, size, seed
+        ))
+    }
+
+    /// Generate an input into the data dir, returning its path
+    pub fn write(&self, data_dir: &Path, size: usize, seed: u64) -> io::Result<PathBuf> {
+        let path = self.path(data_dir, size, seed);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, self.generate(size, seed))
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+
+        Ok(path)
+    }
+}
+
+/// Every day's generator, sorted by day number
+pub static GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        unit: "depths",
+        default_size: 2000,
+        generate: day1,
+    },
+    Generator {
+        day: 2,
+        unit: "commands",
+        default_size: 1000,
+        generate: day2,
+    },
+    Generator {
+        day: 3,
+        unit: "report lines",
+        default_size: 1000,
+        generate: day3,
+    },
+    Generator {
+        day: 4,
+        unit: "boards",
+        default_size: 100,
+        generate: day4,
+    },
+    Generator {
+        day: 5,
+        unit: "vent lines",
+        default_size: 500,
+        generate: day5,
+    },
+    Generator {
+        day: 6,
+        unit: "fish",
+        default_size: 300,
+        generate: day6,
+    },
+    Generator {
+        day: 7,
+        unit: "crabs",
+        default_size: 1000,
+        generate: day7,
+    },
+    Generator {
+        day: 8,
+        unit: "displays",
+        default_size: 200,
+        generate: day8,
+    },
+    Generator {
+        day: 9,
+        unit: "rows and columns",
+        default_size: 100,
+        generate: day9,
+    },
+    Generator {
+        day: 10,
+        unit: "lines",
+        default_size: 100,
+        generate: day10,
+    },
+    Generator {
+        day: 11,
+        unit: "rows and columns",
+        default_size: 10,
+        generate: day11,
+    },
+    Generator {
+        day: 12,
+        unit: "tunnels",
+        default_size: 24,
+        generate: day12,
+    },
+    Generator {
+        day: 13,
+        unit: "dots",
+        default_size: 800,
+        generate: day13,
+    },
+    Generator {
+        day: 14,
+        unit: "template elements",
+        default_size: 20,
+        generate: day14,
+    },
+];
+
+/// Look up the generator for a day
+pub fn get(day: u32) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+fn join<T: ToString>(items: impl IntoIterator<Item = T>, separator: &str) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Depths wandering downwards
+fn day1(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100..=200);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        writeln!(input, "{}", depth).unwrap();
+        depth = (depth + rng.range(-20..=30)).max(0);
+    }
+
+    input
+}
+
+/// Commands that never take the submarine above the surface
+fn day2(rng: &mut Rng, size: usize) -> String {
+    let mut depth = 0;
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let x = rng.range(1..=9);
+        let direction = match rng.below(3) {
+            0 => "forward",
+            1 if depth >= x => "up",
+            _ => "down",
+        };
+        match direction {
+            "up" => depth -= x,
+            "down" => depth += x,
+            _ => {}
+        }
+
+        writeln!(input, "{} {}", direction, x).unwrap();
+    }
+
+    input
+}
+
+/// Distinct binary numbers, redrawn until the CO2 scrubber rating never
+/// keeps a bit that no number has
+fn day3(rng: &mut Rng, size: usize) -> String {
+    let bits = (usize::BITS - size.leading_zeros() + 1).max(12);
+
+    loop {
+        let mut seen = HashSet::new();
+        let mut input = String::new();
+
+        while seen.len() < size {
+            let number = rng.below(1 << bits);
+            if seen.insert(number) {
+                writeln!(input, "{:0width$b}", number, width = bits as usize).unwrap();
+            }
+        }
+
+        if size == 0 || day3::part2(&input).is_ok_and(|solution| solution.is_ok()) {
+            return input;
+        }
+    }
+}
+
+/// Turn on which a board of 25 numbers completes a row or column
+fn winning_turn(board: &[u32], turns: &[usize]) -> usize {
+    let turn = |x: usize, y: usize| turns[board[y * 5 + x] as usize];
+
+    let rows = (0..5).map(|y| (0..5).map(|x| turn(x, y)).max().unwrap());
+    let columns = (0..5).map(|x| (0..5).map(|y| turn(x, y)).max().unwrap());
+
+    rows.chain(columns).min().unwrap()
+}
+
+/// Every number from 0 to 99 drawn once, so every board wins, with a single
+/// board winning first and a single one winning last
+fn day4(rng: &mut Rng, size: usize) -> String {
+    let mut draws: Vec<u32> = (0..100).collect();
+    rng.shuffle(&mut draws);
+    let mut turns = vec![0; 100];
+    for (turn, &number) in draws.iter().enumerate() {
+        turns[number as usize] = turn;
+    }
+
+    let board = |rng: &mut Rng| {
+        let mut numbers: Vec<u32> = (0..100).collect();
+        rng.shuffle(&mut numbers);
+        numbers.truncate(25);
+        numbers
+    };
+
+    // Leave room for the other boards to win in between
+    let (mut boards, wins) = loop {
+        let boards: Vec<_> = (0..size).map(|_| board(rng)).collect();
+        let wins: Vec<_> = boards.iter().map(|b| winning_turn(b, &turns)).collect();
+        let spread = wins.iter().max().zip(wins.iter().min()).map(|(l, f)| l - f);
+
+        if size < 2 || spread >= Some(2) {
+            break (boards, wins);
+        }
+    };
+
+    if size >= 2 {
+        let (first, last) = (*wins.iter().min().unwrap(), *wins.iter().max().unwrap());
+        let first_board = wins.iter().position(|&win| win == first).unwrap();
+        let last_board = wins.iter().position(|&win| win == last).unwrap();
+
+        for (i, numbers) in boards.iter_mut().enumerate() {
+            if i != first_board && i != last_board {
+                while !(first + 1..last).contains(&winning_turn(numbers, &turns)) {
+                    *numbers = board(rng);
+                }
+            }
+        }
+    }
+
+    let mut input = join(&draws, ",");
+    input.push('\n');
+    for board in boards {
+        input.push('\n');
+        for row in board.chunks(5) {
+            let row: Vec<_> = row.iter().map(|n| format!("{:>2}", n)).collect();
+            writeln!(input, "{}", row.join(" ")).unwrap();
+        }
+    }
+
+    input
+}
+
+/// Horizontal, vertical and diagonal lines on a 1000x1000 sea floor
+fn day5(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let (dx, dy) = *rng.choose(&[(1, 0), (0, 1), (1, 1), (1, -1)]);
+        let length = rng.range(1..=300);
+
+        // Keep both ends inside the floor
+        let x1 = rng.range(0..=999 - length);
+        let y1 = match dy {
+            -1 => rng.range(length..=999),
+            0 => rng.range(0..=999),
+            _ => rng.range(0..=999 - length),
+        };
+        let (mut a, mut b) = ((x1, y1), (x1 + dx * length, y1 + dy * length));
+        if rng.chance(1, 2) {
+            (a, b) = (b, a);
+        }
+
+        writeln!(input, "{},{} -> {},{}", a.0, a.1, b.0, b.1).unwrap();
+    }
+
+    input
+}
+
+/// Timers of fish that have not spawned yet
+fn day6(rng: &mut Rng, size: usize) -> String {
+    let timers = (0..size).map(|_| rng.range(1..=5));
+    join(timers, ",") + "\n"
+}
+
+fn day7(rng: &mut Rng, size: usize) -> String {
+    let crabs = (0..size).map(|_| rng.range(0..=1999));
+    join(crabs, ",") + "\n"
+}
+
+/// Segments of each digit on a correctly wired display
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// Displays wired at random, each showing all ten digits in a random order
+/// and four digits of output
+fn day8(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut wiring: Vec<char> = ('a'..='g').collect();
+        rng.shuffle(&mut wiring);
+
+        let pattern = |rng: &mut Rng, digit: usize| {
+            let mut wires: Vec<char> = DIGITS[digit]
+                .chars()
+                .map(|c| wiring[c as usize - 'a' as usize])
+                .collect();
+            rng.shuffle(&mut wires);
+            wires.into_iter().collect::<String>()
+        };
+
+        let mut digits: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut digits);
+        let patterns: Vec<_> = digits.into_iter().map(|d| pattern(rng, d)).collect();
+        let output: Vec<_> = (0..4)
+            .map(|_| {
+                let digit = rng.index(10);
+                pattern(rng, digit)
+            })
+            .collect();
+
+        writeln!(input, "{} | {}", patterns.join(" "), output.join(" ")).unwrap();
+    }
+
+    input
+}
+
+/// Walls of nines splitting the map into basins, each sloping up from a
+/// single low point
+fn day9(rng: &mut Rng, size: usize) -> String {
+    let walls = Grid::from_fn(size, size, |_, _| rng.chance(1, 4));
+    let mut heights = Grid::new(size, size, 9);
+    let mut basin = Grid::new(size, size, false);
+
+    for start in walls.positions() {
+        if walls[start] || basin[start] {
+            continue;
+        }
+
+        let mut cells = vec![start];
+        let mut queue = vec![start];
+        basin[start] = true;
+        while let Some((x, y)) = queue.pop() {
+            for next in walls.neighbours4(x, y) {
+                if !walls[next] && !basin[next] {
+                    basin[next] = true;
+                    cells.push(next);
+                    queue.push(next);
+                }
+            }
+        }
+
+        // Each step away from the low point climbs, so no other cell of the
+        // basin is lower than all its neighbours
+        let low_point = *rng.choose(&cells);
+        let slope = rng.range(1..=2) as u32;
+        let mut distance = Grid::new(size, size, None);
+        let mut queue = VecDeque::from([low_point]);
+        distance[low_point] = Some(0);
+        while let Some((x, y)) = queue.pop_front() {
+            let steps = distance[(x, y)].unwrap();
+            heights[(x, y)] = (steps * slope).min(8);
+
+            for next in walls.neighbours4(x, y) {
+                if !walls[next] && distance[next].is_none() {
+                    distance[next] = Some(steps + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    heights.to_string()
+}
+
+const OPENING: [char; 4] = ['(', '[', '{', '<'];
+const CLOSING: [char; 4] = [')', ']', '}', '>'];
+/// Most chunks open at once, which keeps completion scores within a `u64`
+const MAX_OPEN: usize = 20;
+
+/// Corrupted and incomplete lines, an odd number of them incomplete
+fn day10(rng: &mut Rng, size: usize) -> String {
+    let incomplete = ((size / 2) | 1).min(size);
+    let mut kinds: Vec<bool> = (0..size).map(|i| i < incomplete).collect();
+    rng.shuffle(&mut kinds);
+
+    let mut input = String::new();
+    for is_incomplete in kinds {
+        let length = rng.range(40..=110);
+        let mut stack = vec![];
+        let mut to_corrupt = !is_incomplete;
+
+        for _ in 0..length {
+            if stack.is_empty() || (stack.len() < MAX_OPEN && rng.chance(1, 2)) {
+                let bracket = rng.index(4);
+                stack.push(bracket);
+                input.push(OPENING[bracket]);
+            } else {
+                let mut bracket = stack.pop().unwrap();
+                if to_corrupt && rng.chance(1, 10) {
+                    bracket = (bracket + rng.range(1..=3) as usize) % 4;
+                    to_corrupt = false;
+                }
+                input.push(CLOSING[bracket]);
+            }
+        }
+
+        // Corrupt lines that got through without closing the wrong chunk at
+        // their end, and leave the others open
+        if to_corrupt {
+            let bracket = stack.last().map_or(0, |&open| (open + 1) % 4);
+            input.push(CLOSING[bracket]);
+        } else if is_incomplete && stack.is_empty() {
+            input.push(OPENING[rng.index(4)]);
+        }
+        input.push('\n');
+    }
+
+    input
+}
+
+/// Energy levels of a square grid of octopuses, redrawn until they
+/// synchronize
+///
+/// Most large grids end up flashing in a cycle instead, so after a thousand
+/// draws the last one is kept even though part 2 has no answer.
+fn day11(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+
+    for _ in 0..1000 {
+        input.clear();
+        for _ in 0..size {
+            for _ in 0..size {
+                write!(input, "{}", rng.below(10)).unwrap();
+            }
+            input.push('\n');
+        }
+
+        if size == 0 || day11::part2(&input).is_ok_and(|solution| solution.is_ok()) {
+            break;
+        }
+    }
+
+    input
+}
+
+/// Name of `letters` letters for a cave that is not in `taken` yet, which
+/// takes fewer caves than there are such names
+fn cave_name(rng: &mut Rng, big: bool, letters: usize, taken: &HashSet<String>) -> String {
+    loop {
+        let name: String = (0..letters)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect();
+        let name = if big { name.to_uppercase() } else { name };
+
+        if !taken.contains(&name) {
+            return name;
+        }
+    }
+}
+
+/// Connected caves with one big cave for every four small ones and no two
+/// big caves next to each other, which would make the paths endless
+///
+/// The number of paths grows very quickly with the number of tunnels.
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let others = size / 2;
+    let mut caves = vec!["start".to_string(), "end".to_string()];
+
+    // Two letters like the puzzle's, more once the caves would fill over
+    // half the names
+    let mut letters = 2;
+    while 26usize.pow(letters as u32) < 2 * (others + caves.len()) {
+        letters += 1;
+    }
+
+    let mut taken: HashSet<_> = caves.iter().cloned().collect();
+    for i in 0..others {
+        let name = cave_name(rng, i % 5 == 4, letters, &taken);
+        taken.insert(name.clone());
+        caves.push(name);
+    }
+    let is_big = |cave: usize| caves[cave].chars().all(|c| c.is_ascii_uppercase());
+
+    // Link every cave to one added before it, then add tunnels at random
+    let mut order: Vec<usize> = (1..caves.len()).collect();
+    rng.shuffle(&mut order);
+    order.insert(0, 0);
+
+    let mut tunnels = HashSet::new();
+    for i in 1..order.len() {
+        let candidates: Vec<usize> = order[..i]
+            .iter()
+            .copied()
+            .filter(|&other| !(is_big(order[i]) && is_big(other)))
+            .collect();
+        let other = *rng.choose(&candidates);
+        tunnels.insert((order[i].min(other), order[i].max(other)));
+    }
+
+    for _ in 0..size * 20 {
+        if tunnels.len() >= size {
+            break;
+        }
+
+        let (a, b) = (rng.index(caves.len()), rng.index(caves.len()));
+        if a != b && !(is_big(a) && is_big(b)) {
+            tunnels.insert((a.min(b), a.max(b)));
+        }
+    }
+
+    let mut tunnels: Vec<_> = tunnels.into_iter().collect();
+    tunnels.sort();
+    rng.shuffle(&mut tunnels);
+
+    let mut input = String::new();
+    for (a, b) in tunnels {
+        let (a, b) = if rng.chance(1, 2) { (a, b) } else { (b, a) };
+        writeln!(input, "{}-{}", caves[a], caves[b]).unwrap();
+    }
+
+    input
+}
+
+/// Letters the folded paper spells out
+const DAY13_LETTERS: usize = 8;
+/// Folds along each axis, the first ones being applied last, and as many of
+/// them as a real input has
+const DAY13_X_FOLDS: [i64; 5] = [39, 79, 159, 319, 639];
+const DAY13_Y_FOLDS: [i64; 7] = [6, 13, 27, 55, 111, 223, 447];
+
+/// Dots that fold into eight random letters
+///
+/// Each dot is a lit pixel of the letters unfolded back out, taking either
+/// side of each fold at random. Two dots end up on the far edges so the paper
+/// is exactly as large as the first folds expect. Fewer dots are spread over
+/// a smaller paper folded fewer times.
+fn day13(rng: &mut Rng, size: usize) -> String {
+    let levels = (usize::BITS - size.leading_zeros()) as usize;
+    let x_folds = &DAY13_X_FOLDS[..(levels / 2).clamp(1, DAY13_X_FOLDS.len())];
+    let y_folds = &DAY13_Y_FOLDS[..(levels - levels / 2 + 2).clamp(1, DAY13_Y_FOLDS.len())];
+
+    let alphabet: Vec<char> = Glyphs::alphabet().collect();
+    let text: String = (0..DAY13_LETTERS).map(|_| *rng.choose(&alphabet)).collect();
+    let glyphs = Glyphs::render(&text).unwrap();
+
+    let lit: Vec<(i64, i64)> = (0..glyphs.height())
+        .flat_map(|y| (0..glyphs.width()).map(move |x| (x, y)))
+        .filter(|&(x, y)| glyphs.get(x, y))
+        .map(|(x, y)| (x as i64, y as i64))
+        .collect();
+
+    let unfold = |rng: &mut Rng, coord: i64, folds: &[i64], far_edge: bool| {
+        folds.iter().enumerate().fold(coord, |coord, (i, &fold)| {
+            let mirror = if far_edge {
+                i == folds.len() - 1
+            } else {
+                rng.chance(1, 2)
+            };
+            if mirror {
+                2 * fold - coord
+            } else {
+                coord
+            }
+        })
+    };
+
+    let &left = lit.iter().find(|&&(x, _)| x == 0).unwrap();
+    let &top = lit.iter().find(|&&(_, y)| y == 0).unwrap();
+    let mut dots = vec![
+        (unfold(rng, 0, x_folds, true), left.1),
+        (top.0, unfold(rng, 0, y_folds, true)),
+    ];
+    let mut seen: HashSet<_> = dots.iter().copied().collect();
+
+    // Every lit pixel needs a dot, the rest land on random ones
+    let count = size.max(lit.len());
+    for i in 0..count * 4 {
+        if seen.len() >= count {
+            break;
+        }
+
+        let (x, y) = if i < lit.len() {
+            lit[i]
+        } else {
+            *rng.choose(&lit)
+        };
+        let dot = (
+            unfold(rng, x, x_folds, false),
+            unfold(rng, y, y_folds, false),
+        );
+        if seen.insert(dot) {
+            dots.push(dot);
+        }
+    }
+    rng.shuffle(&mut dots);
+
+    let mut folds: Vec<_> = x_folds
+        .iter()
+        .map(|&x| ('x', x))
+        .chain(y_folds.iter().map(|&y| ('y', y)))
+        .collect();
+    rng.shuffle(&mut folds);
+    // Each axis still folds its largest line first
+    let (mut xs, mut ys) = (x_folds.iter().rev(), y_folds.iter().rev());
+    for (axis, coord) in &mut folds {
+        *coord = *match axis {
+            'x' => xs.next(),
+            _ => ys.next(),
+        }
+        .unwrap();
+    }
+
+    let mut input = String::new();
+    for (x, y) in dots {
+        writeln!(input, "{},{}", x, y).unwrap();
+    }
+    input.push('\n');
+    for (axis, coord) in folds {
+        writeln!(input, "fold along {}={}", axis, coord).unwrap();
+    }
+
+    input
+}
+
+/// Template of ten elements with a rule for every pair of them
+fn day14(rng: &mut Rng, size: usize) -> String {
+    let mut elements: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut elements);
+    elements.truncate(10);
+
+    let mut input: String = (0..size).map(|_| *rng.choose(&elements)).collect();
+    input.push_str("\n\n");
+
+    for &a in &elements {
+        for &b in &elements {
+            writeln!(input, "{}{} -> {}", a, b, rng.choose(&elements)).unwrap();
+        }
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, Answer};
+
+    #[test]
+    fn every_day_has_a_generator() {
+        for pair in GENERATORS.windows(2) {
+            assert!(pair[0].day < pair[1].day);
+        }
+        for puzzle in days::REGISTRY {
+            assert!(get(puzzle.day).is_some(), "day {}", puzzle.day);
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        for generator in GENERATORS {
+            let input = generator.generate(10, 3);
+            assert_eq!(input, generator.generate(10, 3), "day {}", generator.day);
+            assert_ne!(input, generator.generate(10, 4), "day {}", generator.day);
+        }
+    }
+
+    /// Generated inputs parse, and part 1 finds an answer
+    #[test]
+    fn valid_inputs() {
+        for generator in GENERATORS {
+            let puzzle = days::get(generator.day).unwrap();
+
+            for (size, seed) in [(1, 0), (12, 1), (generator.default_size / 4, 2)] {
+                let input = generator.generate(size, seed);
+                let parsed = puzzle.parse(&input).unwrap_or_else(|e| {
+                    panic!("day {} size {} seed {}: {}", generator.day, size, seed, e)
+                });
+                assert!(
+                    puzzle.part1(&parsed).is_ok(),
+                    "day {} size {} seed {}",
+                    generator.day,
+                    size,
+                    seed
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sizes() {
+        let lines = |day, size| get(day).unwrap().generate(size, 5).lines().count();
+        assert_eq!(lines(1, 37), 37);
+        assert_eq!(lines(4, 3), 1 + 3 * 6);
+        assert_eq!(lines(9, 7), 7);
+        assert_eq!(lines(12, 30), 30);
+
+        // More small caves than two letters can name
+        let caves = get(12).unwrap().generate(2000, 5);
+        let names: HashSet<_> = caves.lines().flat_map(|line| line.split('-')).collect();
+        assert_eq!(names.len(), 2 + 1000);
+
+        let report = get(3).unwrap().generate(5000, 5);
+        assert_eq!(report.lines().collect::<HashSet<_>>().len(), 5000);
+        assert!(day3::part2(&report).unwrap().is_ok());
+    }
+
+    #[test]
+    fn folded_letters() {
+        for seed in 0..5 {
+            let input = get(13).unwrap().generate(900, seed);
+            assert_eq!(input.lines().take_while(|l| !l.is_empty()).count(), 900);
+
+            match days::day13::part2(&input).unwrap() {
+                Ok(Answer::Glyphs(glyphs)) => {
+                    let text = glyphs.decode().unwrap();
+                    assert_eq!(text.len(), DAY13_LETTERS);
+                }
+                other => panic!("seed {}: expected letters, found {:?}", seed, other),
+            }
+        }
+    }
+
+    #[test]
+    fn day10_lines() {
+        let input = get(10).unwrap().generate(9, 6);
+        let corrupted = input
+            .lines()
+            .filter(|line| days::day10::part1(line).unwrap() != Ok(0.into()))
+            .count();
+        assert_eq!(corrupted, 4);
+        assert!(days::day10::part2(&input).unwrap().is_ok());
+    }
+}
@@ -0,0 +1,509 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Point or vector on the plane, `y` growing downwards like the rows of a
+/// puzzle map
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// Point or vector in space
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($c:ident),* }) => {
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($c: self.$c + other.$c),* }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($c: self.$c - other.$c),* }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($c: -self.$c),* }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = $point;
+
+            fn mul(self, factor: i64) -> $point {
+                $point { $($c: self.$c * factor),* }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+
+        impl $point {
+            /// Sum of the distances along each axis
+            pub fn manhattan(self, other: $point) -> i64 {
+                0 $(+ (self.$c - other.$c).abs())*
+            }
+
+            /// Largest distance along any axis, the number of king moves
+            /// between the points
+            pub fn chebyshev(self, other: $point) -> i64 {
+                0 $(.max((self.$c - other.$c).abs()))*
+            }
+
+            /// Vector with every coordinate replaced by its sign
+            pub fn signum(self) -> $point {
+                $point { $($c: self.$c.signum()),* }
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point2 {
+        Point2 { x, y }
+    }
+
+    /// Orthogonal neighbours, clockwise from up
+    pub fn neighbours4(self) -> [Point2; 4] {
+        Direction::ALL.map(|direction| self + direction.offset())
+    }
+
+    /// Orthogonal and diagonal neighbours, clockwise from up
+    pub fn neighbours8(self) -> [Point2; 8] {
+        [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .map(|(x, y)| self + Point2::new(x, y))
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Point2 {
+        Point2 { x, y }
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    /// The point turned into each of the 24 orientations of a cube, always
+    /// in the same order and starting with the point itself
+    ///
+    /// Applying the same index to several points rotates them together.
+    pub fn rotations(self) -> [Point3; 24] {
+        let Point3 { x, y, z } = self;
+        let permutations = [
+            ([x, y, z], 1),
+            ([y, z, x], 1),
+            ([z, x, y], 1),
+            ([y, x, z], -1),
+            ([x, z, y], -1),
+            ([z, y, x], -1),
+        ];
+
+        let mut rotations = [Point3::ORIGIN; 24];
+        let mut n = 0;
+        for ([a, b, c], parity) in permutations {
+            for signs in [[1, 1, 1], [1, -1, -1], [-1, 1, -1], [-1, -1, 1]] {
+                // Odd permutations mirror the axes, so flip once more to
+                // keep a rotation
+                let [sa, sb, sc] = signs.map(|sign| sign * parity);
+                rotations[n] = Point3::new(sa * a, sb * b, sc * c);
+                n += 1;
+            }
+        }
+
+        rotations
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Point3 {
+        Point3 { x, y, z }
+    }
+}
+
+/// Step on the plane, up being towards smaller `y`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Unit vector of the step
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// Smallest rectangle holding some points, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds2 {
+    pub min: Point2,
+    pub max: Point2,
+}
+
+impl Bounds2 {
+    /// Rectangle between two opposite corners, in any order
+    pub fn new(a: Point2, b: Point2) -> Bounds2 {
+        Bounds2 {
+            min: Point2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Bounds of the points, `None` when there are none
+    pub fn from_points(points: impl IntoIterator<Item = Point2>) -> Option<Bounds2> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        let mut bounds = Bounds2::new(first, first);
+        points.for_each(|point| bounds.include(point));
+        Some(bounds)
+    }
+
+    /// Grow the rectangle to hold `point`
+    pub fn include(&mut self, point: Point2) {
+        *self = Bounds2::new(
+            Point2::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            Point2::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        );
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    /// Overlap of two rectangles, `None` when they are disjoint
+    pub fn intersection(&self, other: &Bounds2) -> Option<Bounds2> {
+        let min = Point2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+
+        (min.x <= max.x && min.y <= max.y).then_some(Bounds2 { min, max })
+    }
+
+    /// Every point inside, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let Bounds2 { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point2::new(x, y)))
+    }
+}
+
+/// Smallest cuboid holding some points, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds3 {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Bounds3 {
+    /// Cuboid between two opposite corners, in any order
+    pub fn new(a: Point3, b: Point3) -> Bounds3 {
+        Bounds3 {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// Bounds of the points, `None` when there are none
+    pub fn from_points(points: impl IntoIterator<Item = Point3>) -> Option<Bounds3> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        let mut bounds = Bounds3::new(first, first);
+        points.for_each(|point| bounds.include(point));
+        Some(bounds)
+    }
+
+    /// Grow the cuboid to hold `point`
+    pub fn include(&mut self, point: Point3) {
+        let min = Point3::new(
+            self.min.x.min(point.x),
+            self.min.y.min(point.y),
+            self.min.z.min(point.z),
+        );
+        let max = Point3::new(
+            self.max.x.max(point.x),
+            self.max.y.max(point.y),
+            self.max.z.max(point.z),
+        );
+        *self = Bounds3 { min, max };
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// Number of lattice points inside
+    pub fn volume(&self) -> i64 {
+        let size = self.max - self.min;
+        (size.x + 1) * (size.y + 1) * (size.z + 1)
+    }
+
+    /// Overlap of two cuboids, `None` when they are disjoint
+    pub fn intersection(&self, other: &Bounds3) -> Option<Bounds3> {
+        let min = Point3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Point3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Bounds3 { min, max })
+    }
+}
+
+/// Straight line between two points on the plane, both ends included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point2,
+    pub end: Point2,
+}
+
+impl Segment {
+    pub fn new(start: Point2, end: Point2) -> Segment {
+        Segment { start, end }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    /// Whether the segment runs at 45 degrees, a point counts as diagonal
+    pub fn is_diagonal(&self) -> bool {
+        let delta = self.end - self.start;
+        delta.x.abs() == delta.y.abs()
+    }
+
+    /// Every point with integer coordinates on the segment, from start to end
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let delta = self.end - self.start;
+        let steps = gcd(delta.x.abs(), delta.y.abs());
+        let step = if steps == 0 {
+            Point2::ORIGIN
+        } else {
+            Point2::new(delta.x / steps, delta.y / steps)
+        };
+
+        let start = self.start;
+        (0..=steps).map(move |i| start + step * i)
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(-a * 3, Point2::new(-3, -6));
+        assert_eq!((b - a).signum(), Point2::new(1, -1));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::from((1, 1, 1)),
+            Point3::new(2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Point2::new(1, 2).manhattan(Point2::new(4, -2)), 7);
+        assert_eq!(Point2::new(1, 2).chebyshev(Point2::new(4, -2)), 4);
+        assert_eq!(Point3::new(1, -2, 3).manhattan(Point3::ORIGIN), 6);
+        assert_eq!(Point3::new(1, -2, 3).chebyshev(Point3::ORIGIN), 3);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+
+        let total = Direction::ALL
+            .iter()
+            .fold(Point2::ORIGIN, |sum, d| sum + d.offset());
+        assert_eq!(total, Point2::ORIGIN);
+
+        assert_eq!(Point2::ORIGIN.neighbours4()[0], Point2::new(0, -1));
+        assert!(Point2::ORIGIN
+            .neighbours8()
+            .iter()
+            .all(|n| n.chebyshev(Point2::ORIGIN) == 1));
+    }
+
+    #[test]
+    fn rotations() {
+        let point = Point3::new(1, 2, 3);
+        let rotations = point.rotations();
+
+        assert_eq!(rotations[0], point);
+        assert_eq!(rotations.iter().collect::<HashSet<_>>().len(), 24);
+
+        // Rotations keep distances between points rotated alike
+        let other = Point3::new(-4, 0, 7);
+        for (a, b) in rotations.iter().zip(other.rotations()) {
+            assert_eq!(a.manhattan(b), point.manhattan(other));
+        }
+        assert!(rotations.contains(&Point3::new(-2, 1, 3)));
+        assert!(!rotations.contains(&Point3::new(-1, 2, 3)));
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds =
+            Bounds2::from_points([Point2::new(3, 1), Point2::new(0, 4), Point2::new(2, 2)])
+                .unwrap();
+        assert_eq!(bounds, Bounds2::new(Point2::new(3, 4), Point2::new(0, 1)));
+        assert_eq!((bounds.width(), bounds.height()), (4, 4));
+        assert!(bounds.contains(Point2::new(0, 1)));
+        assert!(!bounds.contains(Point2::new(4, 1)));
+        assert_eq!(bounds.points().count(), 16);
+        assert_eq!(bounds.points().nth(4), Some(Point2::new(0, 2)));
+        assert_eq!(Bounds2::from_points([]), None);
+
+        let other = Bounds2::new(Point2::new(3, 4), Point2::new(9, 9));
+        assert_eq!(
+            bounds.intersection(&other),
+            Some(Bounds2::new(Point2::new(3, 4), Point2::new(3, 4)))
+        );
+
+        let cube = Bounds3::new(Point3::new(10, 10, 10), Point3::new(12, 12, 12));
+        assert_eq!(cube.volume(), 27);
+        let overlap = cube
+            .intersection(&Bounds3::new(
+                Point3::new(11, 11, 11),
+                Point3::new(13, 13, 13),
+            ))
+            .unwrap();
+        assert_eq!(overlap.volume(), 8);
+        assert_eq!(
+            cube.intersection(&Bounds3::new(Point3::ORIGIN, Point3::new(9, 20, 20))),
+            None
+        );
+    }
+
+    #[test]
+    fn segments() {
+        let points = |a: (i64, i64), b: (i64, i64)| {
+            Segment::new(a.into(), b.into())
+                .points()
+                .map(|p| (p.x, p.y))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(points((0, 9), (2, 9)), [(0, 9), (1, 9), (2, 9)]);
+        assert_eq!(points((7, 0), (7, 2)), [(7, 0), (7, 1), (7, 2)]);
+        assert_eq!(points((3, 3), (1, 5)), [(3, 3), (2, 4), (1, 5)]);
+        assert_eq!(points((0, 0), (4, 2)), [(0, 0), (2, 1), (4, 2)]);
+        assert_eq!(points((5, 5), (5, 5)), [(5, 5)]);
+
+        let segment = Segment::new(Point2::new(3, 3), Point2::new(1, 5));
+        assert!(segment.is_diagonal());
+        assert!(!segment.is_horizontal() && !segment.is_vertical());
+    }
+}
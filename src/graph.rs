@@ -0,0 +1,326 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Index of a node, in the order the nodes were added
+pub type NodeId = usize;
+
+/// Graph of labelled nodes joined by weighted edges
+///
+/// Each label is stored once and stands for a [`NodeId`], which every search
+/// works with. Edges are directed, an undirected edge is a pair of opposite
+/// ones. Searches that count steps ignore the weights.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    labels: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: Vec<Vec<(NodeId, u64)>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Graph {
+            labels: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph::default()
+    }
+
+    /// Id of the node with `label`, adding the node if it is new
+    pub fn node(&mut self, label: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+
+        let id = self.labels.len();
+        self.labels.push(label.clone());
+        self.ids.insert(label, id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    /// Id of the node with `label`, if there is one
+    pub fn id(&self, label: &N) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn label(&self, id: NodeId) -> &N {
+        &self.labels[id]
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: u64) {
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId, weight: u64) {
+        self.add_edge(a, b, weight);
+        if a != b {
+            self.add_edge(b, a, weight);
+        }
+    }
+
+    /// Outgoing edges of a node with their weights
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, u64)] {
+        &self.edges[id]
+    }
+
+    /// Nodes an edge leads to from `id`
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|&(to, _)| to)
+    }
+
+    /// Number of steps from `start` to every node, `None` where unreachable
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut steps = vec![None; self.len()];
+        let mut queue = VecDeque::from([start]);
+        steps[start] = Some(0);
+
+        while let Some(id) = queue.pop_front() {
+            let next_steps = steps[id].map(|n| n + 1);
+            for next in self.neighbours(id) {
+                if steps[next].is_none() {
+                    steps[next] = next_steps;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        steps
+    }
+
+    /// Nodes reachable from `start` in depth first order, following edges
+    /// in the order they were added
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+
+        while let Some(id) = stack.pop() {
+            if seen[id] {
+                continue;
+            }
+            seen[id] = true;
+            order.push(id);
+
+            // Pushed backwards so the first edge is followed first
+            stack.extend(
+                self.edges[id]
+                    .iter()
+                    .rev()
+                    .map(|&(next, _)| next)
+                    .filter(|&next| !seen[next]),
+            );
+        }
+
+        order
+    }
+
+    /// Shortest weighted distance from `start` to every node, `None` where
+    /// unreachable
+    pub fn dijkstra(&self, start: NodeId) -> Vec<Option<u64>> {
+        self.shortest_paths(start, None).0
+    }
+
+    /// Shortest weighted path from `start` to `goal` with its length
+    pub fn shortest_path(&self, start: NodeId, goal: NodeId) -> Option<(u64, Vec<NodeId>)> {
+        let (distances, previous) = self.shortest_paths(start, Some(goal));
+        let distance = distances[goal]?;
+
+        let mut path = vec![goal];
+        while let Some(id) = previous[*path.last().unwrap()] {
+            path.push(id);
+        }
+        path.reverse();
+
+        Some((distance, path))
+    }
+
+    /// Distances and the node each one was reached from, stopping early once
+    /// `goal` is settled
+    fn shortest_paths(
+        &self,
+        start: NodeId,
+        goal: Option<NodeId>,
+    ) -> (Vec<Option<u64>>, Vec<Option<NodeId>>) {
+        let mut distances = vec![None; self.len()];
+        let mut previous = vec![None; self.len()];
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+        distances[start] = Some(0);
+
+        while let Some(Reverse((distance, id))) = queue.pop() {
+            if distances[id].is_some_and(|best| best < distance) {
+                continue;
+            }
+            if goal == Some(id) {
+                break;
+            }
+
+            for &(next, weight) in self.edges(id) {
+                let next_distance = distance + weight;
+                if distances[next].is_none_or(|best| next_distance < best) {
+                    distances[next] = Some(next_distance);
+                    previous[next] = Some(id);
+                    queue.push(Reverse((next_distance, next)));
+                }
+            }
+        }
+
+        (distances, previous)
+    }
+
+    /// Every node ordered so each edge points forwards, `None` when there is
+    /// a cycle
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut incoming = vec![0; self.len()];
+        for edges in &self.edges {
+            for &(to, _) in edges {
+                incoming[to] += 1;
+            }
+        }
+
+        let mut ready: VecDeque<_> = (0..self.len()).filter(|&id| incoming[id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for next in self.neighbours(id) {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Groups of nodes joined by edges in either direction, each sorted and
+    /// ordered by their first node
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut parent: Vec<NodeId> = (0..self.len()).collect();
+
+        fn root(parent: &mut [NodeId], mut id: NodeId) -> NodeId {
+            while parent[id] != id {
+                parent[id] = parent[parent[id]];
+                id = parent[id];
+            }
+            id
+        }
+
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, _) in edges {
+                let (a, b) = (root(&mut parent, from), root(&mut parent, to));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut components: Vec<Vec<NodeId>> = vec![];
+        let mut index = HashMap::new();
+        for id in 0..self.len() {
+            let root = root(&mut parent, id);
+            let i = *index.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[i].push(id);
+        }
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -> b -> d, a -> c -> d, e -> f with weights
+    fn sample() -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        for (from, to, weight) in [
+            ("a", "b", 1),
+            ("a", "c", 5),
+            ("b", "d", 7),
+            ("c", "d", 1),
+            ("e", "f", 1),
+        ] {
+            let (from, to) = (graph.node(from), graph.node(to));
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn interning() {
+        let mut graph = sample();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.id(&"c"), Some(2));
+        assert_eq!(graph.node("c"), 2);
+        assert_eq!(graph.node("g"), 6);
+        assert_eq!(graph.label(3), &"d");
+        assert_eq!(graph.id(&"h"), None);
+
+        let (a, g) = (graph.id(&"a").unwrap(), graph.id(&"g").unwrap());
+        graph.add_undirected_edge(a, g, 2);
+        assert_eq!(graph.edges(g), [(a, 2)]);
+        assert_eq!(graph.neighbours(a).collect::<Vec<_>>(), [1, 2, g]);
+    }
+
+    #[test]
+    fn searches() {
+        let graph = sample();
+        let a = graph.id(&"a").unwrap();
+
+        assert_eq!(
+            graph.bfs(a),
+            [Some(0), Some(1), Some(1), Some(2), None, None]
+        );
+        assert_eq!(graph.dfs(a), [0, 1, 3, 2]);
+
+        assert_eq!(
+            graph.dijkstra(a),
+            [Some(0), Some(1), Some(5), Some(6), None, None]
+        );
+        assert_eq!(graph.shortest_path(a, 3), Some((6, vec![0, 2, 3])));
+        assert_eq!(graph.shortest_path(a, 5), None);
+        assert_eq!(graph.shortest_path(a, a), Some((0, vec![a])));
+    }
+
+    #[test]
+    fn topological_sort() {
+        let mut graph = sample();
+        assert_eq!(graph.topological_sort(), Some(vec![0, 4, 1, 2, 5, 3]));
+
+        graph.add_edge(3, 0, 1);
+        assert_eq!(graph.topological_sort(), None);
+    }
+
+    #[test]
+    fn components() {
+        let mut graph = sample();
+        let g = graph.node("g");
+
+        assert_eq!(graph.components(), [vec![0, 1, 2, 3], vec![4, 5], vec![g]]);
+
+        graph.add_undirected_edge(5, 3, 1);
+        assert_eq!(graph.components(), [vec![0, 1, 2, 3, 4, 5], vec![g]]);
+    }
+}
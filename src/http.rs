@@ -0,0 +1,519 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Identifies the tool to the puzzle website, as its operators ask of
+/// automated requests
+pub const USER_AGENT: &str = "advent2021 (https://github.com/grant0417/advent2021)";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Parts of an `http://` URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl Url {
+    pub fn parse(url: &str) -> io::Result<Url> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+
+        let rest = match url.split_once("://") {
+            Some(("http", rest)) => rest,
+            _ => return Err(invalid(format!("{}: expected an http:// url", url))),
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|_| invalid(format!("{}: invalid port", url)))?,
+            ),
+            None => (authority, 80),
+        };
+
+        if host.is_empty() {
+            return Err(invalid(format!("{}: missing host", url)));
+        }
+
+        Ok(Url {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Status line and headers, without the body
+    fn parse_head(head: &str) -> io::Result<Response> {
+        let mut lines = head.split("\r\n");
+        let status = lines
+            .next()
+            .and_then(|line| line.split(' ').nth(1))
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| invalid_data("malformed http status line"))?;
+
+        let headers: Vec<_> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        Ok(Response {
+            status,
+            headers,
+            body: String::new(),
+        })
+    }
+
+    fn parse(raw: &[u8]) -> io::Result<Response> {
+        let split = raw
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .ok_or_else(|| invalid_data("malformed http response"))?;
+        let mut response = Response::parse_head(&String::from_utf8_lossy(&raw[..split]))?;
+        let body = &raw[split + 4..];
+
+        let body = if response
+            .header("Transfer-Encoding")
+            .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"))
+        {
+            decode_chunked(body).ok_or_else(|| invalid_data("malformed chunked body"))?
+        } else if let Some(length) = response.header("Content-Length") {
+            let length: usize = length
+                .parse()
+                .map_err(|_| invalid_data("malformed content length"))?;
+            body.get(..length)
+                .ok_or_else(|| invalid_data("truncated http body"))?
+                .to_vec()
+        } else {
+            body.to_vec()
+        };
+
+        response.body = String::from_utf8_lossy(&body).into_owned();
+        Ok(response)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn decode_chunked(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = vec![];
+
+    loop {
+        let line_end = body.windows(2).position(|w| w == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..line_end]).ok()?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        body = &body[line_end + 2..];
+
+        if size == 0 {
+            return Some(decoded);
+        }
+
+        decoded.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+/// Send a request and read the whole response
+///
+/// Plain http is spoken directly, https goes through `curl` as the standard
+/// library has no TLS. See [`check_client`].
+pub fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    if url.starts_with("https://") {
+        return curl(method, url, headers, body);
+    }
+
+    let url = Url::parse(url)?;
+
+    let mut stream = TcpStream::connect((url.host.as_str(), url.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nConnection: close\r\n",
+        method, url.path, url.host, USER_AGENT
+    );
+    for (key, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", key, value));
+    }
+    if let Some(body) = body {
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or(""));
+
+    stream.write_all(request.as_bytes())?;
+
+    let mut raw = vec![];
+    stream.read_to_end(&mut raw)?;
+
+    Response::parse(&raw)
+}
+
+/// Check that requests to `base_url` can be sent, which for https takes the
+/// `curl` command
+///
+/// Run before the first request so a missing `curl` fails the command at
+/// once, rather than after waiting for the throttle.
+pub fn check_client(base_url: &str) -> io::Result<()> {
+    if !base_url.starts_with("https://") {
+        return Url::parse(base_url).map(|_| ());
+    }
+
+    match Command::new("curl").arg("--version").output() {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(_) => Err(io::Error::other("`curl --version` failed")),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(curl_missing()),
+        Err(e) => Err(e),
+    }
+}
+
+fn curl_missing() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        "https requests need the `curl` command, which was not found: install curl or set \
+         `base_url` to an http:// url",
+    )
+}
+
+/// Send a request with the `curl` command
+///
+/// Everything but the fixed options is passed as a config on standard input,
+/// so the session cookie never shows on the command line. The headers are
+/// dumped ahead of the body, and the size of every header block received,
+/// redirects and proxies included, is written after it to find where the
+/// last block ends.
+fn curl(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    let quote = |value: &str| {
+        let escaped = value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+        format!("\"{}\"", escaped)
+    };
+
+    let mut config = format!("url = {}\nrequest = {}\n", quote(url), quote(method));
+    for (key, value) in headers {
+        config.push_str(&format!(
+            "header = {}\n",
+            quote(&format!("{}: {}", key, value))
+        ));
+    }
+    if let Some(body) = body {
+        config.push_str(&format!("data-raw = {}\n", quote(body)));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--max-time"])
+        .arg(TIMEOUT.as_secs().to_string())
+        .args(["--user-agent", USER_AGENT, "--dump-header", "-"])
+        .args(["--write-out", "\\n%{size_header}", "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => curl_missing(),
+            _ => e,
+        })?;
+
+    child.stdin.take().unwrap().write_all(config.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("curl: {}", message.trim())));
+    }
+
+    let raw = &output.stdout;
+    let (head_size, body_end) = raw
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .and_then(|i| {
+            let size = std::str::from_utf8(&raw[i + 1..]).ok()?.parse().ok()?;
+            Some((size, i))
+        })
+        .filter(|&(size, end)| size <= end)
+        .ok_or_else(|| invalid_data("malformed curl output"))?;
+
+    let head = String::from_utf8_lossy(&raw[..head_size]);
+    let last = head
+        .split("\r\n\r\n")
+        .filter(|block| !block.is_empty())
+        .last()
+        .ok_or_else(|| invalid_data("malformed http response"))?;
+
+    let mut response = Response::parse_head(last)?;
+    response.body = String::from_utf8_lossy(&raw[head_size..body_end]).into_owned();
+    Ok(response)
+}
+
+/// Keeps a minimum delay between requests, even across runs, by recording
+/// the time of the last request in a stamp file
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    stamp: PathBuf,
+    delay: Duration,
+}
+
+impl Throttle {
+    pub fn new(stamp: impl Into<PathBuf>, delay: Duration) -> Throttle {
+        Throttle {
+            stamp: stamp.into(),
+            delay,
+        }
+    }
+
+    /// Sleep until the delay since the last request has passed, then record
+    /// a new request
+    pub fn wait(&self) -> io::Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+        let last = fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .map(Duration::from_millis);
+
+        if let Some(remaining) =
+            last.and_then(|last| last.saturating_add(self.delay).checked_sub(now))
+        {
+            thread::sleep(remaining);
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        if let Some(parent) = self.stamp.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Rounded up so the truncated stamp never makes the next delay shorter
+        fs::write(&self.stamp, (now.as_millis() + 1).to_string())
+    }
+}
+
+/// Local stand-in for the puzzle website
+#[cfg(test)]
+pub(crate) mod test_server {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    /// Request received by the stand-in server
+    #[derive(Debug, Clone)]
+    pub struct Received {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Received {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    pub struct TestServer {
+        pub url: String,
+        pub received: Arc<Mutex<Vec<Received>>>,
+    }
+
+    /// Serve each `(status, body)` response in turn, one per connection
+    pub fn serve(responses: Vec<(u16, String)>) -> TestServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(vec![]));
+
+        let log = Arc::clone(&received);
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split_whitespace();
+                let method = parts.next().unwrap_or("").to_string();
+                let path = parts.next().unwrap_or("").to_string();
+
+                let mut headers = vec![];
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    match line.trim_end().split_once(':') {
+                        Some((key, value)) => {
+                            headers.push((key.trim().to_string(), value.trim().to_string()))
+                        }
+                        None => break,
+                    }
+                }
+
+                let length = headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("Content-Length"))
+                    .map_or(0, |(_, value)| value.parse().unwrap());
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                log.lock().unwrap().push(Received {
+                    method,
+                    path,
+                    headers,
+                    body: String::from_utf8(request_body).unwrap(),
+                });
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        TestServer { url, received }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process, time::Instant};
+
+    #[test]
+    fn parse_url() {
+        assert_eq!(
+            Url::parse("http://localhost:8080/2021/day/1/input").unwrap(),
+            Url {
+                host: "localhost".to_string(),
+                port: 8080,
+                path: "/2021/day/1/input".to_string(),
+            }
+        );
+        assert_eq!(Url::parse("http://example.com").unwrap().port, 80);
+        assert_eq!(Url::parse("http://example.com").unwrap().path, "/");
+        assert!(Url::parse("https://example.com").is_err());
+        assert!(Url::parse("example.com").is_err());
+        assert!(check_client("http://localhost:8080").is_ok());
+        assert!(check_client("ftp://localhost").is_err());
+    }
+
+    #[test]
+    fn parse_chunked_response() {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nabcd\r\n2\r\nef\r\n0\r\n\r\n";
+        let response = Response::parse(raw).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "abcdef");
+    }
+
+    #[test]
+    fn request_round_trip() {
+        let server = test_server::serve(vec![(201, "created".to_string())]);
+
+        let response = request(
+            "POST",
+            &format!("{}/submit", server.url),
+            &[("Cookie", "session=abc")],
+            Some("a=1"),
+        )
+        .unwrap();
+
+        assert_eq!(response.status, 201);
+        assert_eq!(response.body, "created");
+
+        let received = server.received.lock().unwrap();
+        assert_eq!(received[0].method, "POST");
+        assert_eq!(received[0].path, "/submit");
+        assert_eq!(received[0].header("Cookie"), Some("session=abc"));
+        assert_eq!(received[0].header("User-Agent"), Some(USER_AGENT));
+        assert_eq!(received[0].body, "a=1");
+    }
+
+    #[test]
+    fn throttle_waits_between_requests() {
+        let stamp = env::temp_dir().join(format!("advent2021-throttle-{}", process::id()));
+        let _ = fs::remove_file(&stamp);
+
+        let throttle = Throttle::new(&stamp, Duration::from_millis(200));
+        let start = Instant::now();
+        throttle.wait().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(200));
+
+        throttle.wait().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        fs::remove_file(&stamp).unwrap();
+    }
+
+    #[test]
+    #[ignore = "needs the curl command, run with `cargo test -- --ignored`"]
+    fn curl_round_trip() {
+        check_client("https://example.com").expect("curl is installed");
+
+        let server = test_server::serve(vec![(200, "a \"quoted\"\nbody\n".to_string())]);
+        let response = curl(
+            "POST",
+            &format!("{}/submit", server.url),
+            &[("Cookie", "session=abc")],
+            Some("level=1&answer=@1"),
+        )
+        .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("Content-Length"), Some("16"));
+        assert_eq!(response.body, "a \"quoted\"\nbody\n");
+
+        let received = server.received.lock().unwrap();
+        assert_eq!(received[0].method, "POST");
+        assert_eq!(received[0].header("Cookie"), Some("session=abc"));
+        assert_eq!(received[0].header("User-Agent"), Some(USER_AGENT));
+        assert_eq!(received[0].body, "level=1&answer=@1");
+    }
+}
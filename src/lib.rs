@@ -1,8 +1,25 @@
-#![feature(test)]
-
-extern crate test;
-
+pub mod alloc;
+pub mod answer;
+pub mod bench;
+pub mod config;
 pub mod days;
+pub mod download;
+pub mod executor;
+pub mod generate;
+pub mod geometry;
+pub mod graph;
+pub mod http;
+pub mod reference;
+pub mod repl;
+pub mod report;
+pub mod scaffold;
+pub mod serve;
+pub mod stream;
+pub mod submit;
 pub mod util;
+pub mod verify;
+pub mod watch;
 
+pub use answer::*;
+pub use config::*;
 pub use util::*;
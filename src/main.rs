@@ -0,0 +1,784 @@
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+use advent2021::{
+    alloc::{self, Allocs},
+    bench::{self, format_duration, BenchConfig, Phase},
+    data_dir, days,
+    download::Downloader,
+    executor, generate, get_real, get_test, normalize, read_input,
+    repl::{self, Repl},
+    report::{self, Baseline, Report},
+    scaffold,
+    serve::{self, Server},
+    submit::{GuessLog, Submitter},
+    verify::{self, Manifest, Outcome},
+    watch::{self, Watcher},
+    Answer, Config,
+};
+
+const USAGE: &str = "\
+Usage: advent2021 <DAY|all> [PART] [--input <real|test|PATH|->]
+       advent2021 bench <DAY|all> [PART] [--input <real|test|PATH|->]
+       advent2021 bench-report [--dir <DIR>] [--threshold <PCT>] [--save-baseline]
+       advent2021 download <DAY>...
+       advent2021 examples <DAY>
+       advent2021 generate <DAY|all> [--size <N>] [--seed <SEED>]
+       advent2021 new-day <DAY>
+       advent2021 repl [DAY]
+       advent2021 run-all [--threads <N>]
+       advent2021 serve [--addr <ADDR>] [--max-body <BYTES>] [--timeout <SECS>]
+       advent2021 submit <DAY> <PART>
+       advent2021 verify [DAY]...
+       advent2021 watch <DAY>
+
+Commands:
+  bench         Time parsing and each part over many runs, after a warm-up
+  bench-report  Bench every day on its real input, write report.md and
+                report.json into DIR [default: bench] and fail when a median
+                grew more than PCT percent [default: 10] over the baseline
+                saved in DIR/baseline.txt by --save-baseline
+  download      Download real inputs into the data dir, never replacing one
+  examples      Check a day's examples against their expected answers
+  generate      Write a random input of N units [default: about the size of a
+                real input] to <data dir>/generated/dayDAY/sizeN-seedSEED.txt,
+                the same for every SEED [default: 0]
+  new-day       Create src/days/dayN.rs from the template, register it and
+                create its empty inputs, unless any of them exists
+  repl          Explore a day interactively: load or paste an input, run the
+                parts, change their parameters and print the parsed input,
+                `help` listing the commands
+  run-all       Solve every day on its real input on N threads [default: one
+                per core] and print a table of the answers checked against
+                <data dir>/answers.txt, with the wall and total solve time
+  serve         Answer HTTP requests on ADDR [default: 127.0.0.1:2021] with
+                JSON: GET /days lists the days and POST /days/DAY/parts/PART
+                solves the input in a body of up to BYTES bytes [default:
+                1048576], answering 504 past SECS seconds [default: 10]
+  submit        Solve a part on the real input and submit the answer, unless an
+                earlier guess in <data dir>/guesses.txt rules it out
+  verify        Check real answers against <data dir>/answers.txt, every
+                implemented day when none is given
+  watch         Check a day's examples, then solve its real input, again each
+                time src/days/dayN.rs or its inputs change
+
+Arguments:
+  DAY     Day to solve, or `all` for every implemented day
+  PART    Part to solve (1 or 2), both parts when omitted
+
+Options:
+  -i, --input <SOURCE>  Where to read the puzzle input from [default: real]
+                          real  <data dir>/real/dayN.txt
+                          test  first example in <data dir>/test/dayN/
+                          -     standard input
+                          PATH  any other file
+  -h, --help            Print this help
+
+The data dir is `data`, unless overridden by ADVENT_DATA_DIR or `data_dir` in
+advent.conf (or the file named by ADVENT_CONFIG).";
+
+/// Where the puzzle input comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    Real,
+    Test,
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    fn parse(s: &str) -> Source {
+        match s {
+            "real" => Source::Real,
+            "test" => Source::Test,
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    fn load(&self, day: u32) -> Result<String, String> {
+        match self {
+            Source::Real => get_real(day).map_err(|e| e.to_string()),
+            Source::Test => get_test(day).map_err(|e| e.to_string()),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("failed to read stdin: {}", e))?;
+                Ok(normalize(&input))
+            }
+            Source::File(path) => read_input(path).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Command {
+    Run(RunArgs),
+    Bench(RunArgs),
+    BenchReport(ReportArgs),
+    Download(Vec<u32>),
+    Examples(u32),
+    Generate(GenerateArgs),
+    NewDay(u32),
+    Repl(Option<u32>),
+    RunAll(usize),
+    Serve(ServeArgs),
+    Submit(u32, u32),
+    Verify(Vec<u32>),
+    Watch(u32),
+}
+
+impl Command {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+        let mut args = args.into_iter().peekable();
+
+        match args.peek().map(String::as_str) {
+            Some("bench") => Ok(Command::Bench(RunArgs::parse(args.skip(1))?)),
+            Some("bench-report") => Ok(Command::BenchReport(ReportArgs::parse(args.skip(1))?)),
+            Some("download") => {
+                let days = args
+                    .skip(1)
+                    .map(|day| parse_day(&day))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if days.is_empty() {
+                    return Err("missing day".to_string());
+                }
+                Ok(Command::Download(days))
+            }
+            Some("examples") => Ok(Command::Examples(implemented_day(args.skip(1))?)),
+            Some("generate") => Ok(Command::Generate(GenerateArgs::parse(args.skip(1))?)),
+            Some("new-day") => {
+                let args: Vec<_> = args.skip(1).collect();
+                match args.as_slice() {
+                    [day] => Ok(Command::NewDay(parse_day(day)?)),
+                    _ => Err("expected a day".to_string()),
+                }
+            }
+            Some("repl") => {
+                let args: Vec<_> = args.skip(1).collect();
+                match args.as_slice() {
+                    [] => Ok(Command::Repl(None)),
+                    _ => Ok(Command::Repl(Some(implemented_day(args)?))),
+                }
+            }
+            Some("run-all") => {
+                let args: Vec<_> = args.skip(1).collect();
+                match args.as_slice() {
+                    [] => Ok(Command::RunAll(executor::default_threads())),
+                    [flag, threads] if flag == "--threads" => threads
+                        .parse()
+                        .ok()
+                        .filter(|&threads| threads > 0)
+                        .map(Command::RunAll)
+                        .ok_or_else(|| format!("invalid thread count: {}", threads)),
+                    [flag] if flag == "--threads" => Err("--threads requires a value".to_string()),
+                    [arg, ..] => Err(format!("unexpected argument: {}", arg)),
+                }
+            }
+            Some("serve") => Ok(Command::Serve(ServeArgs::parse(args.skip(1))?)),
+            Some("submit") => {
+                let args: Vec<_> = args.skip(1).collect();
+                let (day, part) = match args.as_slice() {
+                    [day, part] => (parse_day(day)?, parse_part(part)?),
+                    _ => return Err("expected a day and a part".to_string()),
+                };
+                if days::get(day).is_none() {
+                    return Err(format!("day {} is not implemented", day));
+                }
+                Ok(Command::Submit(day, part))
+            }
+            Some("verify") => {
+                let days = args
+                    .skip(1)
+                    .map(|day| {
+                        let day = parse_day(&day)?;
+                        match days::get(day) {
+                            Some(_) => Ok(day),
+                            None => Err(format!("day {} is not implemented", day)),
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Command::Verify(days))
+            }
+            Some("watch") => Ok(Command::Watch(implemented_day(args.skip(1))?)),
+            _ => Ok(Command::Run(RunArgs::parse(args)?)),
+        }
+    }
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    match day.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("invalid day: {}", day)),
+    }
+}
+
+/// The one argument of a command taking a single implemented day
+fn implemented_day(args: impl IntoIterator<Item = String>) -> Result<u32, String> {
+    let args: Vec<_> = args.into_iter().collect();
+    let day = match args.as_slice() {
+        [day] => parse_day(day)?,
+        _ => return Err("expected a day".to_string()),
+    };
+
+    match days::get(day) {
+        Some(_) => Ok(day),
+        None => Err(format!("day {} is not implemented", day)),
+    }
+}
+
+fn parse_part(part: &str) -> Result<u32, String> {
+    match part {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part: {}", part)),
+    }
+}
+
+#[derive(Debug)]
+struct RunArgs {
+    days: Vec<u32>,
+    parts: Vec<u32>,
+    source: Source,
+}
+
+impl RunArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<RunArgs, String> {
+        let mut positional = vec![];
+        let mut source = Source::Real;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    process::exit(0);
+                }
+                "-i" | "--input" => {
+                    let value = args.next().ok_or("--input requires a value")?;
+                    source = Source::parse(&value);
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+
+        let days = match positional.next().as_deref() {
+            Some("all") => days::REGISTRY.iter().map(|puzzle| puzzle.day).collect(),
+            Some(day) => {
+                let day = parse_day(day)?;
+                if days::get(day).is_none() {
+                    return Err(format!("day {} is not implemented", day));
+                }
+                vec![day]
+            }
+            None => return Err("missing day".to_string()),
+        };
+
+        let parts = match positional.next() {
+            Some(part) => vec![parse_part(&part)?],
+            None => vec![1, 2],
+        };
+
+        if let Some(arg) = positional.next() {
+            return Err(format!("unexpected argument: {}", arg));
+        }
+
+        if days.len() > 1 && matches!(source, Source::Stdin | Source::File(_)) {
+            return Err("an explicit input can only be used with a single day".to_string());
+        }
+
+        Ok(RunArgs {
+            days,
+            parts,
+            source,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ReportArgs {
+    dir: PathBuf,
+    threshold: f64,
+    save_baseline: bool,
+}
+
+impl ReportArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<ReportArgs, String> {
+        let mut report = ReportArgs {
+            dir: PathBuf::from("bench"),
+            threshold: report::DEFAULT_THRESHOLD,
+            save_baseline: false,
+        };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--dir" => report.dir = PathBuf::from(args.next().ok_or("--dir requires a value")?),
+                "--threshold" => {
+                    let value = args.next().ok_or("--threshold requires a value")?;
+                    report.threshold = value
+                        .parse()
+                        .ok()
+                        .filter(|threshold: &f64| *threshold >= 0.0)
+                        .ok_or_else(|| format!("invalid threshold: {}", value))?;
+                }
+                "--save-baseline" => report.save_baseline = true,
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[derive(Debug)]
+struct GenerateArgs {
+    days: Vec<u32>,
+    size: Option<usize>,
+    seed: u64,
+}
+
+impl GenerateArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<GenerateArgs, String> {
+        let mut days = None;
+        let mut size = None;
+        let mut seed = 0;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--size" => {
+                    let value = args.next().ok_or("--size requires a value")?;
+                    size = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|&size| size > 0)
+                            .ok_or_else(|| format!("invalid size: {}", value))?,
+                    );
+                }
+                "--seed" => {
+                    let value = args.next().ok_or("--seed requires a value")?;
+                    seed = value
+                        .parse()
+                        .map_err(|_| format!("invalid seed: {}", value))?;
+                }
+                "all" if days.is_none() => {
+                    days = Some(generate::GENERATORS.iter().map(|g| g.day).collect())
+                }
+                _ if days.is_none() => {
+                    let day = parse_day(&arg)?;
+                    if generate::get(day).is_none() {
+                        return Err(format!("day {} has no generator", day));
+                    }
+                    days = Some(vec![day]);
+                }
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+
+        Ok(GenerateArgs {
+            days: days.ok_or("missing day")?,
+            size,
+            seed,
+        })
+    }
+}
+
+/// How long a step took, with what it allocated when allocations are counted
+fn cost(start: Instant, allocs: Option<Allocs>) -> String {
+    let elapsed = format_duration(start.elapsed());
+    match allocs {
+        Some(allocs) => format!("{}, {}", elapsed, allocs),
+        None => elapsed,
+    }
+}
+
+#[derive(Debug)]
+struct ServeArgs {
+    addr: String,
+    max_body: usize,
+    timeout: Duration,
+}
+
+impl ServeArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<ServeArgs, String> {
+        let mut serve = ServeArgs {
+            addr: "127.0.0.1:2021".to_string(),
+            max_body: serve::DEFAULT_MAX_BODY,
+            timeout: serve::DEFAULT_SOLVE_TIMEOUT,
+        };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--addr" => serve.addr = args.next().ok_or("--addr requires a value")?,
+                "--max-body" => {
+                    let value = args.next().ok_or("--max-body requires a value")?;
+                    serve.max_body = value
+                        .parse()
+                        .map_err(|_| format!("invalid size: {}", value))?;
+                }
+                "--timeout" => {
+                    let value = args.next().ok_or("--timeout requires a value")?;
+                    serve.timeout = value
+                        .parse()
+                        .ok()
+                        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                        .filter(|timeout| !timeout.is_zero())
+                        .ok_or_else(|| format!("invalid timeout: {}", value))?;
+                }
+                _ => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+
+        Ok(serve)
+    }
+}
+
+fn run(args: &RunArgs) -> Result<(), String> {
+    for &day in &args.days {
+        let puzzle = days::get(day).unwrap();
+        let input = args.source.load(day)?;
+
+        let start = Instant::now();
+        let (parsed, allocs) = alloc::measure(|| puzzle.parse(&input));
+        let parsed = parsed.map_err(|e| e.to_string())?;
+        println!("Day {} parse ({})", day, cost(start, allocs));
+
+        // Both parts share the parsed input
+        for &part in &args.parts {
+            let start = Instant::now();
+            let (solution, allocs) = alloc::measure(|| puzzle.run(part, &parsed).unwrap());
+            let elapsed = cost(start, allocs);
+
+            match solution {
+                Ok(Answer::Glyphs(glyphs)) => {
+                    let letters = glyphs.decode().unwrap_or_else(|| "?".to_string());
+                    println!("Day {} part {}: {} ({})", day, part, letters, elapsed);
+                    print!("{}", glyphs);
+                }
+                Ok(answer) => println!("Day {} part {}: {} ({})", day, part, answer, elapsed),
+                Err(e) => println!("Day {} part {}: {} ({})", day, part, e, elapsed),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn bench(args: &RunArgs) -> Result<(), String> {
+    let config = BenchConfig::default();
+
+    for &day in &args.days {
+        let puzzle = days::get(day).unwrap();
+        let input = args.source.load(day)?;
+
+        let stats = bench::bench_parse(&config, puzzle, &input).map_err(|e| e.to_string())?;
+        println!("Day {} parse: {}", day, stats);
+
+        let parsed = puzzle.parse(&input).unwrap();
+        for &part in &args.parts {
+            let stats = bench::bench_part(&config, puzzle, part, &parsed).unwrap();
+            println!("Day {} part {}: {}", day, part, stats);
+        }
+    }
+
+    Ok(())
+}
+
+fn bench_report(args: &ReportArgs) -> Result<(), String> {
+    let config = BenchConfig::default();
+    let baseline_path = args.dir.join("baseline.txt");
+    let baseline = Baseline::load(&baseline_path).map_err(|e| e.to_string())?;
+
+    let mut results = vec![];
+    for puzzle in days::REGISTRY {
+        let input = get_real(puzzle.day).map_err(|e| e.to_string())?;
+        let parsed = puzzle.parse(&input).map_err(|e| e.to_string())?;
+
+        for phase in Phase::ALL {
+            eprintln!("Benchmarking day {} {}", puzzle.day, phase);
+            let stats = match phase {
+                Phase::Parse => bench::bench_parse(&config, puzzle, &input).unwrap(),
+                Phase::Part1 => bench::bench_part(&config, puzzle, 1, &parsed).unwrap(),
+                Phase::Part2 => bench::bench_part(&config, puzzle, 2, &parsed).unwrap(),
+            };
+            results.push((puzzle.day, phase, stats));
+        }
+    }
+
+    let report = Report::new(results, baseline.as_ref(), args.threshold);
+    let markdown = report.markdown();
+    print!("{}", markdown);
+
+    let write = |name: &str, contents: &str| {
+        let path = args.dir.join(name);
+        fs::create_dir_all(&args.dir)
+            .and_then(|_| fs::write(&path, contents))
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    };
+    write("report.md", &markdown)?;
+    write("report.json", &report.json())?;
+
+    if args.save_baseline {
+        Baseline::from_entries(&report.entries)
+            .save(&baseline_path)
+            .map_err(|e| format!("{}: {}", baseline_path.display(), e))?;
+        println!("Baseline saved to {}", baseline_path.display());
+    } else if baseline.is_none() {
+        println!(
+            "No baseline in {}, nothing to compare",
+            baseline_path.display()
+        );
+    }
+
+    match report.regressions().len() {
+        0 => Ok(()),
+        n => Err(format!(
+            "{} phase(s) regressed more than {}% over the baseline",
+            n, args.threshold
+        )),
+    }
+}
+
+fn download(days: &[u32]) -> Result<(), String> {
+    let config = Config::load().map_err(|e| e.to_string())?;
+    let downloader = Downloader::from_config(&config).map_err(|e| e.to_string())?;
+
+    for &day in days {
+        let path = downloader.download(day).map_err(|e| e.to_string())?;
+        println!("Day {} input saved to {}", day, path.display());
+    }
+
+    Ok(())
+}
+
+fn examples(day: u32) -> Result<(), String> {
+    let examples = advent2021::examples(day).map_err(|e| e.to_string())?;
+    let checks = verify::check_examples(days::get(day).unwrap(), &examples);
+    if checks.is_empty() {
+        println!("Day {} has no examples with answers", day);
+    }
+
+    let mut failed = 0;
+    for check in &checks {
+        let outcome = match &check.outcome {
+            Outcome::Fail { expected, found } => {
+                failed += 1;
+                format!("FAILED, expected {:?} but found {:?}", expected, found)
+            }
+            outcome => outcome.to_string(),
+        };
+        println!(
+            "Day {} example {} part {}: {}",
+            day, check.name, check.part, outcome
+        );
+    }
+
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} example part(s) failed", n)),
+    }
+}
+
+fn generate(args: &GenerateArgs) -> Result<(), String> {
+    let data_dir = data_dir().map_err(|e| e.to_string())?;
+
+    for &day in &args.days {
+        let generator = generate::get(day).unwrap();
+        let size = args.size.unwrap_or(generator.default_size);
+
+        let path = generator
+            .write(&data_dir, size, args.seed)
+            .map_err(|e| e.to_string())?;
+        println!(
+            "Day {}: {} {} written to {}",
+            day,
+            size,
+            generator.unit,
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn new_day(day: u32) -> Result<(), String> {
+    let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let data_dir = data_dir().map_err(|e| e.to_string())?;
+
+    for path in scaffold::new_day(&src_dir, &data_dir, day).map_err(|e| e.to_string())? {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn repl(day: Option<u32>) -> Result<(), String> {
+    let mut session = Repl::new();
+    if let Some(day) = day {
+        let mut no_paste = io::empty();
+        match session.eval(&format!("day {}", day), &mut no_paste) {
+            Ok(out) => println!("{}", out),
+            Err(e) => println!("error: {}", e),
+        }
+    }
+
+    repl::run(io::stdin().lock(), io::stdout(), session).map_err(|e| e.to_string())
+}
+
+fn run_all(threads: usize) -> Result<(), String> {
+    let manifest = Manifest::load().map_err(|e| e.to_string())?;
+    let summary = executor::run_all(days::REGISTRY, &manifest, threads, |day| {
+        get_real(day).map_err(|e| e.to_string())
+    });
+    print!("{}", summary.table());
+
+    match summary.failures().count() {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) failed or did not run", n)),
+    }
+}
+
+fn serve(args: &ServeArgs) -> Result<(), String> {
+    let server = Server::bind(&args.addr, args.max_body, args.timeout)
+        .map_err(|e| format!("{}: {}", args.addr, e))?;
+    println!(
+        "Serving on http://{}",
+        server.local_addr().map_err(|e| e.to_string())?
+    );
+
+    server.run().map_err(|e| e.to_string())
+}
+
+fn submit(day: u32, part: u32) -> Result<(), String> {
+    let config = Config::load().map_err(|e| e.to_string())?;
+    let submitter = Submitter::from_config(&config).map_err(|e| e.to_string())?;
+    let mut log = GuessLog::open().map_err(|e| e.to_string())?;
+
+    let input = get_real(day).map_err(|e| e.to_string())?;
+    let answer = days::get(day)
+        .unwrap()
+        .solve(part, &input)
+        .unwrap()
+        .map_err(|e| e.to_string())?
+        .map_err(|_| format!("day {} part {} has no solution to submit", day, part))?;
+
+    let verdict = submitter
+        .submit(&mut log, day, part, &answer)
+        .map_err(|e| e.to_string())?;
+    println!(
+        "Day {} part {}: {} is {}",
+        day,
+        part,
+        answer.submission().unwrap(),
+        verdict
+    );
+
+    Ok(())
+}
+
+fn verify(days: &[u32]) -> Result<(), String> {
+    let manifest = Manifest::load().map_err(|e| e.to_string())?;
+    let puzzles: Vec<_> = days::REGISTRY
+        .iter()
+        .filter(|puzzle| days.is_empty() || days.contains(&puzzle.day))
+        .copied()
+        .collect();
+
+    let mut failed = 0;
+    for check in verify::verify(&manifest, &puzzles) {
+        println!("Day {} part {}: {}", check.day, check.part, check.outcome);
+        if matches!(check.outcome, Outcome::Fail { .. } | Outcome::Error(_)) {
+            failed += 1;
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) did not match the answers manifest", n)),
+    }
+}
+
+/// Run the runner through cargo, rebuilding it first when its source changed,
+/// and tell whether it succeeded
+fn cargo_run(root: &Path, args: &[String]) -> Result<bool, String> {
+    let mut cargo = process::Command::new(option_env!("CARGO").unwrap_or("cargo"));
+    cargo
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(root.join("Cargo.toml"));
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+
+    let status = cargo
+        .arg("--")
+        .args(args)
+        .status()
+        .map_err(|e| format!("failed to run cargo: {}", e))?;
+    Ok(status.success())
+}
+
+fn watch(day: u32) -> Result<(), String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let paths = watch::day_paths(&root.join("src"), day).map_err(|e| e.to_string())?;
+    let mut watcher = Watcher::new(paths);
+
+    loop {
+        // Clear the screen and move the cursor back to the top
+        print!("\x1b[2J\x1b[H");
+        println!("Day {}, examples", day);
+        io::stdout().flush().map_err(|e| e.to_string())?;
+
+        // The real input is only worth solving once the examples pass
+        if cargo_run(root, &["examples".to_string(), day.to_string()])? {
+            println!("\nDay {}, real input", day);
+            cargo_run(root, &[day.to_string()])?;
+        }
+        println!("\nWaiting for changes, Ctrl-C to stop");
+
+        while !watcher.changed() {
+            thread::sleep(watch::POLL_INTERVAL);
+        }
+    }
+}
+
+fn main() {
+    let command = match Command::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let result = match &command {
+        Command::Run(args) => run(args),
+        Command::Bench(args) => bench(args),
+        Command::BenchReport(args) => bench_report(args),
+        Command::Download(days) => download(days),
+        Command::Examples(day) => examples(*day),
+        Command::Generate(args) => generate(args),
+        Command::NewDay(day) => new_day(*day),
+        Command::Repl(day) => repl(*day),
+        Command::RunAll(threads) => run_all(*threads),
+        Command::Serve(args) => serve(args),
+        Command::Submit(day, part) => submit(*day, *part),
+        Command::Verify(days) => verify(days),
+        Command::Watch(day) => watch(*day),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
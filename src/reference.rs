@@ -0,0 +1,963 @@
+use crate::{days, generate, Answer, Glyphs, NoSolution, ParseError, Solution};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// Brute force solver of one day, written as plainly as possible to check
+/// the real solver against on generated inputs
+///
+/// References only take inputs from the day's generator and panic on
+/// anything else. A part without a reference is not checked.
+#[derive(Debug, Clone, Copy)]
+pub struct Reference {
+    pub day: u32,
+    /// Largest generated input the brute force solves in a moment
+    pub max_size: usize,
+    pub part1: Option<fn(&str) -> Solution>,
+    pub part2: Option<fn(&str) -> Solution>,
+}
+
+impl Reference {
+    /// Solve a part, `None` if it has no reference
+    pub fn solve(&self, part: u32, input: &str) -> Option<Solution> {
+        match part {
+            1 => self.part1.map(|solve| solve(input)),
+            2 => self.part2.map(|solve| solve(input)),
+            _ => None,
+        }
+    }
+}
+
+/// Every day's reference, sorted by day number
+pub static REFERENCES: &[Reference] = &[
+    Reference {
+        day: 1,
+        max_size: 50,
+        part1: Some(day1_part1),
+        part2: Some(day1_part2),
+    },
+    Reference {
+        day: 2,
+        max_size: 50,
+        part1: Some(day2_part1),
+        part2: Some(day2_part2),
+    },
+    Reference {
+        day: 3,
+        max_size: 40,
+        part1: Some(day3_part1),
+        part2: Some(day3_part2),
+    },
+    Reference {
+        day: 4,
+        max_size: 6,
+        part1: Some(day4_part1),
+        part2: Some(day4_part2),
+    },
+    Reference {
+        day: 5,
+        max_size: 30,
+        part1: Some(day5_part1),
+        part2: Some(day5_part2),
+    },
+    Reference {
+        day: 6,
+        max_size: 5,
+        part1: Some(day6_part1),
+        part2: Some(day6_part2),
+    },
+    Reference {
+        day: 7,
+        max_size: 30,
+        part1: Some(day7_part1),
+        part2: Some(day7_part2),
+    },
+    Reference {
+        day: 8,
+        max_size: 8,
+        part1: Some(day8_part1),
+        part2: Some(day8_part2),
+    },
+    Reference {
+        day: 9,
+        max_size: 15,
+        part1: Some(day9_part1),
+        part2: Some(day9_part2),
+    },
+    Reference {
+        day: 10,
+        max_size: 15,
+        part1: Some(day10_part1),
+        part2: Some(day10_part2),
+    },
+    Reference {
+        day: 11,
+        max_size: 8,
+        part1: Some(day11_part1),
+        part2: Some(day11_part2),
+    },
+    Reference {
+        day: 12,
+        max_size: 12,
+        part1: Some(day12_part1),
+        part2: Some(day12_part2),
+    },
+    Reference {
+        day: 13,
+        max_size: 200,
+        part1: Some(day13_part1),
+        part2: Some(day13_part2),
+    },
+    // Part 2 is not solved yet
+    Reference {
+        day: 14,
+        max_size: 10,
+        part1: Some(day14_part1),
+        part2: None,
+    },
+];
+
+/// Look up the reference for a day
+pub fn get(day: u32) -> Option<&'static Reference> {
+    REFERENCES.iter().find(|reference| reference.day == day)
+}
+
+/// Generated input on which a day's solver and its reference disagree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u32,
+    pub part: u32,
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub expected: Solution,
+    pub found: Result<Solution, ParseError>,
+}
+
+fn show(solution: &Solution) -> String {
+    match solution {
+        Ok(Answer::Glyphs(glyphs)) => glyphs.decode().unwrap_or_else(|| glyphs.to_string()),
+        Ok(answer) => answer.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = match &self.found {
+            Ok(solution) => show(solution),
+            Err(e) => e.to_string(),
+        };
+
+        writeln!(
+            f,
+            "day {} part {} with seed {} and size {}: expected {}, found {}",
+            self.day,
+            self.part,
+            self.seed,
+            self.size,
+            show(&self.expected),
+            found
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+/// Size of the input generated for a seed, cycling through every size up to
+/// the reference's largest
+pub fn size_for(reference: &Reference, seed: u64) -> usize {
+    1 + (seed % reference.max_size as u64) as usize
+}
+
+/// Compare a day's solver with `reference` on `runs` generated inputs, with
+/// seeds counting up from `seed`
+pub fn compare(reference: &Reference, seed: u64, runs: u64) -> Vec<Mismatch> {
+    let puzzle = days::get(reference.day).expect("reference of a day that is not implemented");
+    let generator = generate::get(reference.day).expect("reference of a day without generator");
+    let mut mismatches = vec![];
+
+    for seed in (0..runs).map(|run| seed.wrapping_add(run)) {
+        let size = size_for(reference, seed);
+        let input = generator.generate(size, seed);
+        let parsed = puzzle.parse(&input);
+
+        for part in [1, 2] {
+            let expected = match reference.solve(part, &input) {
+                Some(expected) => expected,
+                None => continue,
+            };
+            let found = match &parsed {
+                Ok(parsed) => Ok(puzzle.run(part, parsed).unwrap()),
+                Err(e) => Err(e.clone()),
+            };
+
+            if found.as_ref() != Ok(&expected) {
+                mismatches.push(Mismatch {
+                    day: reference.day,
+                    part,
+                    seed,
+                    size,
+                    input: input.clone(),
+                    expected,
+                    found,
+                });
+            }
+        }
+    }
+
+    mismatches
+}
+
+fn numbers<T: std::str::FromStr>(text: &str, separator: char) -> Vec<T> {
+    text.trim()
+        .split(separator)
+        .map(|n| n.trim().parse().ok().unwrap())
+        .collect()
+}
+
+fn count_increases(values: &[i64]) -> Solution {
+    let increases = (1..values.len())
+        .filter(|&i| values[i] > values[i - 1])
+        .count();
+
+    Ok(increases.into())
+}
+
+fn day1_part1(input: &str) -> Solution {
+    count_increases(&numbers(input, '\n'))
+}
+
+fn day1_part2(input: &str) -> Solution {
+    let depths: Vec<i64> = numbers(input, '\n');
+    let sums: Vec<i64> = depths.windows(3).map(|w| w[0] + w[1] + w[2]).collect();
+
+    count_increases(&sums)
+}
+
+fn day2(input: &str, aimed: bool) -> Solution {
+    let (mut position, mut depth, mut aim) = (0i64, 0i64, 0i64);
+
+    for line in input.lines() {
+        let (command, x) = line.split_once(' ').unwrap();
+        let x: i64 = x.parse().unwrap();
+
+        match (command, aimed) {
+            ("forward", false) => position += x,
+            ("forward", true) => {
+                position += x;
+                depth += aim * x;
+            }
+            ("down", false) => depth += x,
+            ("up", false) => depth -= x,
+            ("down", true) => aim += x,
+            ("up", true) => aim -= x,
+            _ => panic!("unknown command {}", command),
+        }
+    }
+
+    Ok((position * depth).into())
+}
+
+fn day2_part1(input: &str) -> Solution {
+    day2(input, false)
+}
+
+fn day2_part2(input: &str) -> Solution {
+    day2(input, true)
+}
+
+fn ones_at(lines: &[&str], i: usize) -> usize {
+    lines
+        .iter()
+        .filter(|line| line.as_bytes()[i] == b'1')
+        .count()
+}
+
+fn day3_part1(input: &str) -> Solution {
+    let lines: Vec<&str> = input.lines().collect();
+    let bits = lines[0].len();
+    let (mut gamma, mut epsilon) = (String::new(), String::new());
+
+    for i in 0..bits {
+        let ones = ones_at(&lines, i);
+        let most_common = ones > lines.len() - ones;
+        gamma.push(if most_common { '1' } else { '0' });
+        epsilon.push(if most_common { '0' } else { '1' });
+    }
+
+    let gamma = i64::from_str_radix(&gamma, 2).unwrap();
+    let epsilon = i64::from_str_radix(&epsilon, 2).unwrap();
+    Ok((gamma * epsilon).into())
+}
+
+/// Filter the report bit by bit as the puzzle describes, `keep_ones` telling
+/// from the counts of ones and zeros which lines stay
+fn day3_rating(input: &str, keep_ones: fn(usize, usize) -> bool) -> Result<i64, NoSolution> {
+    let mut lines: Vec<&str> = input.lines().collect();
+
+    for i in 0..lines[0].len() {
+        if lines.len() == 1 {
+            break;
+        }
+
+        let ones = ones_at(&lines, i);
+        let bit = if keep_ones(ones, lines.len() - ones) {
+            b'1'
+        } else {
+            b'0'
+        };
+        lines.retain(|line| line.as_bytes()[i] == bit);
+    }
+
+    match lines.as_slice() {
+        [line] => Ok(i64::from_str_radix(line, 2).unwrap()),
+        _ => Err(NoSolution),
+    }
+}
+
+fn day3_part2(input: &str) -> Solution {
+    let oxygen = day3_rating(input, |ones, zeros| ones >= zeros)?;
+    let co2 = day3_rating(input, |ones, zeros| ones < zeros)?;
+
+    Ok((oxygen * co2).into())
+}
+
+/// Turn each board wins on and its score, boards in input order
+fn day4_wins(input: &str) -> Vec<(usize, u64)> {
+    let mut blocks = input.split("\n\n");
+    let draws: Vec<u64> = numbers(blocks.next().unwrap(), ',');
+    let boards: Vec<Vec<u64>> = blocks
+        .map(|block| {
+            block
+                .split_whitespace()
+                .map(|n| n.parse().unwrap())
+                .collect()
+        })
+        .collect();
+
+    boards
+        .iter()
+        .map(|board| {
+            (0..draws.len())
+                .find_map(|turn| {
+                    let drawn = &draws[..=turn];
+                    let marked = |i: usize| drawn.contains(&board[i]);
+                    let row = (0..5).any(|y| (0..5).all(|x| marked(y * 5 + x)));
+                    let column = (0..5).any(|x| (0..5).all(|y| marked(y * 5 + x)));
+
+                    (row || column).then(|| {
+                        let unmarked: u64 = (0..25).filter(|&i| !marked(i)).map(|i| board[i]).sum();
+                        (turn, unmarked * draws[turn])
+                    })
+                })
+                .unwrap()
+        })
+        .collect()
+}
+
+fn day4_part1(input: &str) -> Solution {
+    let wins = day4_wins(input);
+    let (_, score) = wins.iter().min_by_key(|(turn, _)| *turn).unwrap();
+
+    Ok((*score).into())
+}
+
+fn day4_part2(input: &str) -> Solution {
+    let wins = day4_wins(input);
+    let (_, score) = wins.iter().max_by_key(|(turn, _)| *turn).unwrap();
+
+    Ok((*score).into())
+}
+
+fn day5(input: &str, diagonals: bool) -> Solution {
+    let mut covered: HashMap<(i64, i64), usize> = HashMap::new();
+
+    for line in input.lines() {
+        let (a, b) = line.split_once(" -> ").unwrap();
+        let [x1, y1]: [i64; 2] = numbers(a, ',').try_into().unwrap();
+        let [x2, y2]: [i64; 2] = numbers(b, ',').try_into().unwrap();
+
+        if !diagonals && x1 != x2 && y1 != y2 {
+            continue;
+        }
+
+        let (dx, dy) = ((x2 - x1).signum(), (y2 - y1).signum());
+        let (mut x, mut y) = (x1, y1);
+        loop {
+            *covered.entry((x, y)).or_default() += 1;
+            if (x, y) == (x2, y2) {
+                break;
+            }
+            (x, y) = (x + dx, y + dy);
+        }
+    }
+
+    Ok(covered.values().filter(|&&count| count >= 2).count().into())
+}
+
+fn day5_part1(input: &str) -> Solution {
+    day5(input, false)
+}
+
+fn day5_part2(input: &str) -> Solution {
+    day5(input, true)
+}
+
+/// Simulate every single fish
+fn day6_part1(input: &str) -> Solution {
+    let mut fish: Vec<u8> = numbers(input, ',');
+
+    for _ in 0..80 {
+        let mut born = 0;
+        for timer in &mut fish {
+            if *timer == 0 {
+                *timer = 6;
+                born += 1;
+            } else {
+                *timer -= 1;
+            }
+        }
+        fish.extend(std::iter::repeat_n(8, born));
+    }
+
+    Ok(fish.len().into())
+}
+
+/// Number of fish a fish with `timer` becomes after `days`, counting each
+/// child's family separately
+fn day6_family(timer: u64, days: u64, known: &mut HashMap<(u64, u64), u64>) -> u64 {
+    if days <= timer {
+        return 1;
+    }
+    if let Some(&family) = known.get(&(timer, days)) {
+        return family;
+    }
+
+    let rest = days - timer - 1;
+    let family = day6_family(6, rest, known) + day6_family(8, rest, known);
+    known.insert((timer, days), family);
+    family
+}
+
+/// Far too many fish to simulate one by one, so each family is counted
+fn day6_part2(input: &str) -> Solution {
+    let mut known = HashMap::new();
+    let fish: u64 = numbers(input, ',')
+        .into_iter()
+        .map(|timer| day6_family(timer, 256, &mut known))
+        .sum();
+
+    Ok(fish.into())
+}
+
+/// The solver already tries every position, so the reference goes straight
+/// to the median for part 1
+fn day7_part1(input: &str) -> Solution {
+    let mut crabs: Vec<i64> = numbers(input, ',');
+    crabs.sort();
+    let median = crabs[crabs.len() / 2];
+
+    Ok(crabs
+        .iter()
+        .map(|crab| (crab - median).abs())
+        .sum::<i64>()
+        .into())
+}
+
+/// The cheapest position for part 2 is within half a step of the mean
+fn day7_part2(input: &str) -> Solution {
+    let crabs: Vec<i64> = numbers(input, ',');
+    let mean = crabs.iter().sum::<i64>() / crabs.len() as i64;
+
+    let fuel = |target: i64| -> i64 {
+        crabs
+            .iter()
+            .map(|crab| {
+                let distance = (crab - target).abs();
+                distance * (distance + 1) / 2
+            })
+            .sum()
+    };
+
+    Ok((mean - 1..=mean + 1).map(fuel).min().unwrap().into())
+}
+
+fn day8_part1(input: &str) -> Solution {
+    let easy = input
+        .lines()
+        .flat_map(|line| line.split_once(" | ").unwrap().1.split_whitespace())
+        .filter(|pattern| [2, 3, 4, 7].contains(&pattern.len()))
+        .count();
+
+    Ok(easy.into())
+}
+
+/// Every order of the seven wires
+fn permutations(wires: &mut Vec<u8>, rest: &[u8], out: &mut Vec<Vec<u8>>) {
+    if rest.is_empty() {
+        out.push(wires.clone());
+    }
+    for (i, &wire) in rest.iter().enumerate() {
+        let mut others = rest.to_vec();
+        others.remove(i);
+        wires.push(wire);
+        permutations(wires, &others, out);
+        wires.pop();
+    }
+}
+
+/// Try every wiring until all ten patterns show a digit
+fn day8_part2(input: &str) -> Solution {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+    let mut wirings = vec![];
+    permutations(&mut vec![], b"abcdefg", &mut wirings);
+
+    let mut sum = 0;
+    for line in input.lines() {
+        let (patterns, output) = line.split_once(" | ").unwrap();
+
+        let digit = |wiring: &[u8], pattern: &str| {
+            let mut segments: Vec<u8> = pattern
+                .bytes()
+                .map(|c| wiring[(c - b'a') as usize])
+                .collect();
+            segments.sort();
+            DIGITS.iter().position(|digit| digit.as_bytes() == segments)
+        };
+        let wiring = wirings
+            .iter()
+            .find(|wiring| {
+                patterns
+                    .split_whitespace()
+                    .all(|p| digit(wiring, p).is_some())
+            })
+            .unwrap();
+
+        sum += output.split_whitespace().fold(0, |value, pattern| {
+            value * 10 + digit(wiring, pattern).unwrap()
+        });
+    }
+
+    Ok(sum.into())
+}
+
+fn day9_heights(input: &str) -> Vec<Vec<u32>> {
+    input
+        .lines()
+        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
+        .collect()
+}
+
+fn day9_neighbours(heights: &[Vec<u32>], x: usize, y: usize) -> Vec<(usize, usize)> {
+    let mut neighbours = vec![];
+    if x > 0 {
+        neighbours.push((x - 1, y));
+    }
+    if y > 0 {
+        neighbours.push((x, y - 1));
+    }
+    if x + 1 < heights[y].len() {
+        neighbours.push((x + 1, y));
+    }
+    if y + 1 < heights.len() {
+        neighbours.push((x, y + 1));
+    }
+    neighbours
+}
+
+fn day9_part1(input: &str) -> Solution {
+    let heights = day9_heights(input);
+    let mut risk = 0;
+
+    for y in 0..heights.len() {
+        for x in 0..heights[y].len() {
+            let height = heights[y][x];
+            let lowest = day9_neighbours(&heights, x, y)
+                .into_iter()
+                .all(|(nx, ny)| heights[ny][nx] > height);
+            if lowest {
+                risk += height + 1;
+            }
+        }
+    }
+
+    Ok(risk.into())
+}
+
+/// Sizes of the areas walled in by nines, without looking for low points
+fn day9_part2(input: &str) -> Solution {
+    let heights = day9_heights(input);
+    let mut seen = HashSet::new();
+    let mut sizes = vec![];
+
+    for y in 0..heights.len() {
+        for x in 0..heights[y].len() {
+            if heights[y][x] == 9 || !seen.insert((x, y)) {
+                continue;
+            }
+
+            let mut stack = vec![(x, y)];
+            let mut size = 0;
+            while let Some((x, y)) = stack.pop() {
+                size += 1;
+                for (nx, ny) in day9_neighbours(&heights, x, y) {
+                    if heights[ny][nx] != 9 && seen.insert((nx, ny)) {
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+    }
+
+    sizes.sort_by(|a, b| b.cmp(a));
+    sizes.resize(sizes.len().max(3), 0);
+    Ok(sizes[..3].iter().product::<usize>().into())
+}
+
+/// Remove matched pairs until none are left, leaving the first wrong closing
+/// bracket or the chunks still open
+fn day10_reduce(line: &str) -> String {
+    let mut line = line.to_string();
+    loop {
+        let before = line.len();
+        for pair in ["()", "[]", "{}", "<>"] {
+            line = line.replace(pair, "");
+        }
+        if line.len() == before {
+            return line;
+        }
+    }
+}
+
+fn day10_part1(input: &str) -> Solution {
+    let score: u64 = input
+        .lines()
+        .map(
+            |line| match day10_reduce(line).chars().find(|c| ")]}>".contains(*c)) {
+                Some(')') => 3,
+                Some(']') => 57,
+                Some('}') => 1197,
+                Some('>') => 25137,
+                _ => 0,
+            },
+        )
+        .sum();
+
+    Ok(score.into())
+}
+
+fn day10_part2(input: &str) -> Solution {
+    let mut scores: Vec<u64> = input
+        .lines()
+        .map(day10_reduce)
+        .filter(|open| !open.contains([')', ']', '}', '>']))
+        .map(|open| {
+            open.chars()
+                .rev()
+                .fold(0, |score, c| score * 5 + "([{<".find(c).unwrap() as u64 + 1)
+        })
+        .collect();
+
+    if scores.is_empty() {
+        return Err(NoSolution);
+    }
+
+    scores.sort();
+    Ok(scores[scores.len() / 2].into())
+}
+
+/// One step of the octopuses, returning how many flashed
+fn day11_step(energy: &mut [Vec<u32>]) -> usize {
+    let (height, width) = (energy.len(), energy[0].len());
+    let mut flashed = vec![vec![false; width]; height];
+
+    for row in energy.iter_mut() {
+        for cell in row {
+            *cell += 1;
+        }
+    }
+
+    // Keep sweeping the grid until nothing more flashes
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for y in 0..height {
+            for x in 0..width {
+                if energy[y][x] > 9 && !flashed[y][x] {
+                    flashed[y][x] = true;
+                    changed = true;
+
+                    for row in &mut energy[y.saturating_sub(1)..(y + 2).min(height)] {
+                        for cell in &mut row[x.saturating_sub(1)..(x + 2).min(width)] {
+                            *cell += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for (y, row) in energy.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            if flashed[y][x] {
+                *cell = 0;
+            }
+        }
+    }
+
+    flashed.iter().flatten().filter(|&&f| f).count()
+}
+
+fn day11_part1(input: &str) -> Solution {
+    let mut energy = day9_heights(input);
+    let flashes: usize = (0..100).map(|_| day11_step(&mut energy)).sum();
+
+    Ok(flashes.into())
+}
+
+fn day11_part2(input: &str) -> Solution {
+    let mut energy = day9_heights(input);
+    let octopuses = energy.len() * energy[0].len();
+    let mut seen = HashSet::new();
+
+    for step in 1.. {
+        if day11_step(&mut energy) == octopuses {
+            return Ok(step.into());
+        }
+        if !seen.insert(energy.clone()) {
+            break;
+        }
+    }
+
+    Err(NoSolution)
+}
+
+/// Walk every path from its last cave to the end, one cave at a time
+fn day12_walk<'a>(tunnels: &[(&'a str, &'a str)], path: &mut Vec<&'a str>, revisit: bool) -> usize {
+    let current = *path.last().unwrap();
+    if current == "end" {
+        return 1;
+    }
+
+    let small = |cave: &str| cave.chars().all(|c| c.is_ascii_lowercase());
+    let visits = |path: &[&str], cave: &str| path.iter().filter(|&&c| c == cave).count();
+    let revisited = path
+        .iter()
+        .any(|&cave| small(cave) && visits(path, cave) > 1);
+
+    let mut paths = 0;
+    for &(a, b) in tunnels {
+        let next = match current {
+            _ if current == a => b,
+            _ if current == b => a,
+            _ => continue,
+        };
+        if next == "start" {
+            continue;
+        }
+        // A small cave is only entered again by the one allowed revisit
+        let can_revisit = revisit && !revisited;
+        if small(next) && visits(path, next) > 0 && !can_revisit {
+            continue;
+        }
+
+        path.push(next);
+        paths += day12_walk(tunnels, path, revisit);
+        path.pop();
+    }
+
+    paths
+}
+
+fn day12(input: &str, revisit: bool) -> Solution {
+    let tunnels: Vec<(&str, &str)> = input
+        .lines()
+        .map(|line| line.split_once('-').unwrap())
+        .collect();
+
+    Ok(day12_walk(&tunnels, &mut vec!["start"], revisit).into())
+}
+
+fn day12_part1(input: &str) -> Solution {
+    day12(input, false)
+}
+
+fn day12_part2(input: &str) -> Solution {
+    day12(input, true)
+}
+
+/// Dots left after the first `folds` folds and the last fold on each axis
+fn day13_fold(input: &str, folds: usize) -> (HashSet<(i64, i64)>, i64, i64) {
+    let (dots, instructions) = input.split_once("\n\n").unwrap();
+    let mut dots: HashSet<(i64, i64)> = dots
+        .lines()
+        .map(|dot| {
+            let [x, y]: [i64; 2] = numbers(dot, ',').try_into().unwrap();
+            (x, y)
+        })
+        .collect();
+    let (mut width, mut height) = (0, 0);
+
+    for instruction in instructions.lines().take(folds) {
+        let (axis, line) = instruction
+            .trim_start_matches("fold along ")
+            .split_once('=')
+            .unwrap();
+        let line: i64 = line.parse().unwrap();
+        let mirror = |n: i64| if n > line { 2 * line - n } else { n };
+
+        dots = dots
+            .into_iter()
+            .map(|(x, y)| match axis {
+                "x" => (mirror(x), y),
+                _ => (x, mirror(y)),
+            })
+            .collect();
+        match axis {
+            "x" => width = line,
+            _ => height = line,
+        }
+    }
+
+    (dots, width, height)
+}
+
+fn day13_part1(input: &str) -> Solution {
+    Ok(day13_fold(input, 1).0.len().into())
+}
+
+fn day13_part2(input: &str) -> Solution {
+    let (dots, width, height) = day13_fold(input, usize::MAX);
+    let mut glyphs = Glyphs::new(width as usize, height as usize);
+    for (x, y) in dots {
+        glyphs.set(x as usize, y as usize, true);
+    }
+
+    Ok(glyphs.into())
+}
+
+/// Build the whole polymer
+fn day14_part1(input: &str) -> Solution {
+    let (template, rules) = input.split_once("\n\n").unwrap();
+    let rules: HashMap<&str, &str> = rules
+        .lines()
+        .map(|rule| rule.split_once(" -> ").unwrap())
+        .collect();
+
+    let mut polymer = template.trim().to_string();
+    for _ in 0..10 {
+        let mut next = String::new();
+        for i in 0..polymer.len() {
+            next.push_str(&polymer[i..i + 1]);
+            if let Some(inserted) = polymer.get(i..i + 2).and_then(|pair| rules.get(pair)) {
+                next.push_str(inserted);
+            }
+        }
+        polymer = next;
+    }
+
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in polymer.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    let (most, least) = (
+        counts.values().max().unwrap(),
+        counts.values().min().unwrap(),
+    );
+
+    Ok((most - least).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    /// First seed of the randomized comparison unless `ADVENT_SEED` names
+    /// another, fixed so every run checks the same inputs
+    const DEFAULT_SEED: u64 = 2021;
+
+    #[test]
+    fn every_day_has_a_reference() {
+        for pair in REFERENCES.windows(2) {
+            assert!(pair[0].day < pair[1].day);
+        }
+        for puzzle in days::REGISTRY {
+            assert!(get(puzzle.day).is_some(), "day {}", puzzle.day);
+        }
+    }
+
+    /// References agree with the answers of the examples
+    #[test]
+    fn references_solve_examples() {
+        for reference in REFERENCES {
+            // Examples of day 12 and 14 are not generated, but have the same
+            // shape
+            for example in crate::examples(reference.day).unwrap() {
+                for part in [1, 2] {
+                    if let (Some(found), Some(expected)) = (
+                        reference.solve(part, &example.input),
+                        example.expected(part),
+                    ) {
+                        let found = crate::normalize(&show(&found));
+                        assert_eq!(found, expected, "day {} part {}", reference.day, part);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Randomized comparison of every day with its reference, from
+    /// [`DEFAULT_SEED`] unless `ADVENT_SEED` names the first seed
+    #[test]
+    fn solvers_match_references() {
+        let seed = match env::var("ADVENT_SEED") {
+            Ok(seed) => seed.parse().expect("ADVENT_SEED must be a number"),
+            Err(_) => DEFAULT_SEED,
+        };
+        // Shown with the output of a failing test, a panic included
+        println!("first seed {}", seed);
+
+        let mismatches: Vec<_> = REFERENCES
+            .iter()
+            .flat_map(|reference| compare(reference, seed, 20))
+            .collect();
+
+        assert!(
+            mismatches.is_empty(),
+            "{} mismatch(es), first seed {}\n\n{}",
+            mismatches.len(),
+            seed,
+            mismatches
+                .iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn reports_mismatch() {
+        let wrong = Reference {
+            day: 1,
+            max_size: 5,
+            part1: Some(|_| Ok((-1).into())),
+            part2: None,
+        };
+
+        let mismatches = compare(&wrong, 40, 3);
+        assert_eq!(mismatches.len(), 3);
+        assert_eq!(
+            (mismatches[0].part, mismatches[0].seed, mismatches[0].size),
+            (1, 40, 1)
+        );
+
+        let shown = mismatches[1].to_string();
+        assert!(shown.starts_with("day 1 part 1 with seed 41 and size 2: expected -1, found "));
+        assert!(shown.ends_with(&mismatches[1].input));
+    }
+}
@@ -0,0 +1,437 @@
+use crate::{
+    bench::{self, format_duration, BenchConfig, Phase},
+    days::{self, Parsed, Puzzle},
+    executor::catch_panic,
+    get_real, get_test, normalize, read_input, real_path, Answer,
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    hint::black_box,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    time::Instant,
+};
+
+const HELP: &str = "\
+Commands:
+  day <DAY>              Pick a day and load its real input when there is one
+  load [real|test|PATH]  Load and parse an input [default: real]
+  paste                  Load the lines typed or pasted next, up to a `.` line
+  reload                 Read and parse the loaded input again
+  run [PART]             Solve a part, both when omitted
+  time [parse|PART]      Bench parsing or a part, both parts when omitted
+  set [NAME [VALUE]]     List the parameters, or set one, `default` resetting
+                         it to what the puzzle asks for
+  show [input|params]    Print the parsed input, the raw input or the
+                         parameters [default: the parsed input]
+  help                   Print this help
+  quit                   Leave, like end of input";
+
+/// Where the loaded input was read from, to read it again
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Origin {
+    Real,
+    Test,
+    File(PathBuf),
+    Pasted,
+}
+
+impl Origin {
+    fn parse(s: &str) -> Origin {
+        match s {
+            "real" => Origin::Real,
+            "test" => Origin::Test,
+            path => Origin::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Read the input of `day`, `None` for pasted text which cannot be read
+    /// again
+    fn read(&self, day: u32) -> Option<io::Result<String>> {
+        match self {
+            Origin::Real => Some(get_real(day)),
+            Origin::Test => Some(get_test(day)),
+            Origin::File(path) => Some(read_input(path)),
+            Origin::Pasted => None,
+        }
+    }
+}
+
+/// State of an interactive session: a day, its input parsed once and the
+/// parameter values its parts run with
+#[derive(Debug, Default)]
+pub struct Repl {
+    puzzle: Option<&'static Puzzle>,
+    origin: Option<Origin>,
+    input: String,
+    parsed: Option<Parsed>,
+    /// Values set by name, used by every part with a parameter of that name
+    values: BTreeMap<String, usize>,
+}
+
+impl Repl {
+    pub fn new() -> Repl {
+        Repl::default()
+    }
+
+    /// Prompt naming the picked day
+    pub fn prompt(&self) -> String {
+        match self.puzzle {
+            Some(puzzle) => format!("day{}> ", puzzle.day),
+            None => "advent> ".to_string(),
+        }
+    }
+
+    /// Run one command line and return what it prints, reading pasted text
+    /// from `lines`
+    pub fn eval(&mut self, line: &str, lines: &mut impl BufRead) -> Result<String, String> {
+        let words: Vec<_> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            [] => Ok(String::new()),
+            ["help"] => Ok(HELP.to_string()),
+            ["day", day] => self.day(day),
+            ["load"] => self.load(Origin::Real),
+            ["load", source] => self.load(Origin::parse(source)),
+            ["paste"] => self.paste(lines),
+            ["reload"] => {
+                let origin = self.origin.clone().ok_or("no input is loaded")?;
+                self.load(origin)
+            }
+            ["run"] => self.run(&[1, 2]),
+            ["run", part] => self.run(&[parse_part(part)?]),
+            ["time"] => self.time(&[Phase::Part1, Phase::Part2]),
+            ["time", phase] => match phase.parse() {
+                Ok(phase) => self.time(&[phase]),
+                Err(_) => Err(format!("expected `parse`, 1 or 2, found `{}`", phase)),
+            },
+            ["set"] | ["show", "params"] => self.params(),
+            ["set", name, value] => self.set(name, value),
+            ["show"] => {
+                let puzzle = self.puzzle()?;
+                Ok(puzzle.show(self.parsed()?))
+            }
+            ["show", "input"] => match self.origin {
+                Some(_) => Ok(self.input.trim_end().to_string()),
+                None => Err("no input is loaded, try `load` or `paste`".to_string()),
+            },
+            [command, ..] => Err(format!(
+                "unknown command or arguments `{}`, try `help`",
+                command
+            )),
+        }
+    }
+
+    fn puzzle(&self) -> Result<&'static Puzzle, String> {
+        self.puzzle
+            .ok_or_else(|| "no day is picked, try `day <DAY>`".to_string())
+    }
+
+    fn parsed(&self) -> Result<&Parsed, String> {
+        self.puzzle()?;
+        self.parsed
+            .as_ref()
+            .ok_or_else(|| "no input is loaded, try `load` or `paste`".to_string())
+    }
+
+    fn day(&mut self, day: &str) -> Result<String, String> {
+        let puzzle = day
+            .parse()
+            .ok()
+            .and_then(days::get)
+            .ok_or_else(|| format!("day {} is not implemented", day))?;
+
+        *self = Repl {
+            puzzle: Some(puzzle),
+            ..Repl::default()
+        };
+
+        let mut out = format!("Day {}: {}", puzzle.day, puzzle.title);
+        if real_path(puzzle.day).is_ok_and(|path| path.is_file()) {
+            out.push('\n');
+            out.push_str(&self.load(Origin::Real)?);
+        }
+        Ok(out)
+    }
+
+    fn load(&mut self, origin: Origin) -> Result<String, String> {
+        let day = self.puzzle()?.day;
+        let input = match origin.read(day) {
+            Some(input) => input.map_err(|e| e.to_string())?,
+            None => self.input.clone(),
+        };
+
+        self.origin = Some(origin);
+        self.parse(input)
+    }
+
+    fn paste(&mut self, lines: &mut impl BufRead) -> Result<String, String> {
+        self.puzzle()?;
+
+        let mut input = String::new();
+        loop {
+            let mut line = String::new();
+            if lines.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+                break;
+            }
+            if line.trim_end() == "." {
+                break;
+            }
+            input.push_str(&line);
+        }
+
+        self.origin = Some(Origin::Pasted);
+        self.parse(normalize(&input))
+    }
+
+    /// Keep an input and parse it, an input that fails to parse being kept to
+    /// show but not solved
+    fn parse(&mut self, input: String) -> Result<String, String> {
+        let puzzle = self.puzzle()?;
+        self.input = input;
+        self.parsed = None;
+
+        let start = Instant::now();
+        let parsed = puzzle.parse(&self.input).map_err(|e| e.to_string())?;
+        let elapsed = start.elapsed();
+        self.parsed = Some(parsed);
+
+        Ok(format!(
+            "Parsed {} line(s) in {}",
+            self.input.lines().count(),
+            format_duration(elapsed)
+        ))
+    }
+
+    /// Values of a part's parameters, in the order the part takes them
+    fn values(&self, part: u32) -> Result<Vec<usize>, String> {
+        Ok(self
+            .puzzle()?
+            .part_params(part)
+            .map(|param| *self.values.get(param.name).unwrap_or(&param.default))
+            .collect())
+    }
+
+    fn run(&self, parts: &[u32]) -> Result<String, String> {
+        let puzzle = self.puzzle()?;
+        let parsed = self.parsed()?;
+        let mut out = String::new();
+
+        for &part in parts {
+            let values = self.values(part)?;
+            let start = Instant::now();
+            let solution = catch_panic(|| puzzle.run_with(part, parsed, &values).unwrap())
+                .map_err(|panic| format!("part {} panicked: {}", part, panic))?;
+            let elapsed = format_duration(start.elapsed());
+
+            match solution {
+                Ok(Answer::Glyphs(glyphs)) => {
+                    let letters = glyphs.decode().unwrap_or_else(|| "?".to_string());
+                    writeln!(out, "Part {}: {} ({})\n{}", part, letters, elapsed, glyphs)
+                }
+                Ok(answer) => writeln!(out, "Part {}: {} ({})", part, answer, elapsed),
+                Err(e) => writeln!(out, "Part {}: {} ({})", part, e, elapsed),
+            }
+            .unwrap();
+        }
+
+        Ok(out.trim_end().to_string())
+    }
+
+    fn time(&self, phases: &[Phase]) -> Result<String, String> {
+        let puzzle = self.puzzle()?;
+        let parsed = self.parsed()?;
+        let config = BenchConfig::default();
+        let mut out = String::new();
+
+        for &phase in phases {
+            let stats = match phase {
+                Phase::Parse => bench::bench_parse(&config, puzzle, &self.input).unwrap(),
+                Phase::Part1 | Phase::Part2 => {
+                    let part = if phase == Phase::Part1 { 1 } else { 2 };
+                    let values = self.values(part)?;
+                    catch_panic(|| {
+                        bench::bench(&config, || {
+                            puzzle.run_with(part, black_box(parsed), &values)
+                        })
+                    })
+                    .map_err(|panic| format!("part {} panicked: {}", part, panic))?
+                }
+            };
+            writeln!(out, "{}: {}", phase, stats).unwrap();
+        }
+
+        Ok(out.trim_end().to_string())
+    }
+
+    fn params(&self) -> Result<String, String> {
+        let puzzle = self.puzzle()?;
+        if puzzle.params.is_empty() {
+            return Ok(format!("Day {} has no parameters", puzzle.day));
+        }
+
+        let mut out = String::new();
+        for param in puzzle.params {
+            let value = match self.values.get(param.name) {
+                Some(value) => format!("{} (default {})", value, param.default),
+                None => param.default.to_string(),
+            };
+            writeln!(out, "Part {} {} = {}", param.part, param.name, value).unwrap();
+        }
+
+        Ok(out.trim_end().to_string())
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<String, String> {
+        let puzzle = self.puzzle()?;
+        if !puzzle.params.iter().any(|param| param.name == name) {
+            return Err(format!("day {} has no parameter `{}`", puzzle.day, name));
+        }
+
+        if value == "default" {
+            self.values.remove(name);
+        } else {
+            let value = value
+                .parse()
+                .map_err(|_| format!("expected a count or `default`, found `{}`", value))?;
+            self.values.insert(name.to_string(), value);
+        }
+
+        self.params()
+    }
+}
+
+fn parse_part(part: &str) -> Result<u32, String> {
+    match part {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("expected part 1 or 2, found `{}`", part)),
+    }
+}
+
+/// Read commands from `input` until `quit` or the end of it, writing a
+/// prompt before each and what they print after
+///
+/// A failed command prints its error and the session goes on.
+pub fn run(mut input: impl BufRead, mut output: impl Write, mut repl: Repl) -> io::Result<()> {
+    loop {
+        write!(output, "{}", repl.prompt())?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || matches!(line.trim(), "quit" | "exit") {
+            return Ok(());
+        }
+
+        match repl.eval(&line, &mut input) {
+            Ok(out) if out.is_empty() => {}
+            Ok(out) => writeln!(output, "{}", out)?,
+            Err(e) => writeln!(output, "error: {}", e)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(commands: &str) -> String {
+        let mut output = vec![];
+        run(commands.as_bytes(), &mut output, Repl::new()).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn paste_and_run() {
+        let output = session("day 6\npaste\n3,4,3,1,2\n.\nrun 1\nset days 18\nrun 1\nquit\nrun\n");
+        assert!(output.contains("day6> Parsed 1 line(s) in "));
+        assert!(output.contains("Part 1: 5934 ("));
+        // Both parts count days
+        assert!(output.contains("Part 1 days = 18 (default 80)\nPart 2 days = 18 (default 256)\n"));
+        assert!(output.contains("Part 1: 26 ("));
+        // Nothing runs after `quit`
+        assert_eq!(output.matches("Part 1: ").count(), 2);
+    }
+
+    #[test]
+    fn show_and_errors() {
+        let mut repl = Repl::new();
+        let mut none = &b""[..];
+
+        assert_eq!(
+            repl.eval("run", &mut none).unwrap_err(),
+            "no day is picked, try `day <DAY>`"
+        );
+        assert!(repl.eval("day 26", &mut none).is_err());
+
+        repl.eval("day 1", &mut none).unwrap();
+        let mut pasted = &b"199\n200\n208\n.\n"[..];
+        repl.eval("paste", &mut pasted).unwrap();
+        assert_eq!(repl.eval("show input", &mut none).unwrap(), "199\n200\n208");
+        assert_eq!(
+            repl.eval("show", &mut none).unwrap(),
+            "[\n    199,\n    200,\n    208,\n]"
+        );
+        assert_eq!(
+            repl.eval("set", &mut none).unwrap(),
+            "Day 1 has no parameters"
+        );
+        assert!(repl.eval("set window 2", &mut none).is_err());
+        assert!(repl.eval("run 3", &mut none).is_err());
+
+        let mut broken = &b"199\nx\n.\n"[..];
+        assert_eq!(
+            repl.eval("paste", &mut broken).unwrap_err(),
+            "day 1, line 2, column 1: expected a number (found `x`)"
+        );
+        assert_eq!(repl.eval("show input", &mut none).unwrap(), "199\nx");
+        assert!(repl.eval("run", &mut none).is_err());
+
+        assert!(repl.eval("frobnicate", &mut none).is_err());
+    }
+
+    // Overflowing only panics with debug assertions
+    #[cfg(debug_assertions)]
+    #[test]
+    fn survives_panics() {
+        let output = session(
+            "day 6\npaste\n3,4,3,1,2\n.\nset days 1000\nrun 1\ntime 2\n\
+             set days default\nrun 1\n",
+        );
+        assert!(output.contains("day6> error: part 1 panicked: attempt to add with overflow\n"));
+        assert!(output.contains("day6> error: part 2 panicked: attempt to add with overflow\n"));
+        assert!(output.contains("Part 1: 5934 ("));
+    }
+
+    #[test]
+    fn load_and_reload() {
+        let mut repl = Repl::new();
+        let mut none = &b""[..];
+
+        let out = repl.eval("day 13", &mut none).unwrap();
+        assert!(out.starts_with("Day 13: Transparent Origami\nParsed "));
+        assert_eq!(
+            repl.eval("run 1", &mut none).unwrap().split(" (").next(),
+            Some("Part 1: 664")
+        );
+
+        repl.eval("load test", &mut none).unwrap();
+        repl.eval("set folds 2", &mut none).unwrap();
+        assert!(repl
+            .eval("run 1", &mut none)
+            .unwrap()
+            .starts_with("Part 1: 16 ("));
+        repl.eval("set folds 3", &mut none).unwrap();
+        assert!(repl
+            .eval("run 1", &mut none)
+            .unwrap()
+            .starts_with("Part 1: no solution"));
+
+        assert!(repl
+            .eval("reload", &mut none)
+            .unwrap()
+            .starts_with("Parsed "));
+        assert!(repl.eval("load no/such/file.txt", &mut none).is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use crate::bench::{format_duration, Phase, Stats};
+use std::{collections::BTreeMap, fmt::Write, fs, io, path::Path, time::Duration};
+
+/// Regression threshold used when none is given, in percent of the baseline
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Benchmark of one day and phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: u32,
+    pub phase: Phase,
+    pub stats: Stats,
+    /// Median of the same phase in the baseline
+    pub baseline: Option<Duration>,
+}
+
+impl Entry {
+    /// Change of the median against the baseline, in percent
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline?.as_secs_f64();
+        (baseline > 0.0).then(|| (self.stats.median.as_secs_f64() / baseline - 1.0) * 100.0)
+    }
+
+    /// Whether the median grew past `threshold` percent of the baseline
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Medians saved from an earlier run, one `DAY PHASE NANOSECONDS` line each
+/// where the phase is `parse`, `1` or `2`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    medians: BTreeMap<(u32, Phase), Duration>,
+}
+
+impl Baseline {
+    pub fn parse(text: &str) -> Result<Baseline, String> {
+        let mut medians = BTreeMap::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, median) = parse_median(line)
+                .ok_or_else(|| format!("line {}: expected `DAY PHASE NANOSECONDS`", i + 1))?;
+
+            medians.insert(key, median);
+        }
+
+        Ok(Baseline { medians })
+    }
+
+    /// Load a baseline, `None` when the file does not exist
+    pub fn load(path: &Path) -> io::Result<Option<Baseline>> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("{}: {}", path.display(), e),
+                ))
+            }
+        };
+
+        Baseline::parse(&text).map(Some).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })
+    }
+
+    pub fn from_entries(entries: &[Entry]) -> Baseline {
+        Baseline {
+            medians: entries
+                .iter()
+                .map(|entry| ((entry.day, entry.phase), entry.stats.median))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, day: u32, phase: Phase) -> Option<Duration> {
+        self.medians.get(&(day, phase)).copied()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut text = String::from("# day phase median in nanoseconds\n");
+        for ((day, phase), median) in &self.medians {
+            writeln!(text, "{} {} {}", day, phase, median.as_nanos()).unwrap();
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, text)
+    }
+}
+
+fn parse_median(line: &str) -> Option<((u32, Phase), Duration)> {
+    let mut fields = line.split_whitespace();
+    let day = fields.next()?.parse().ok()?;
+    let phase = fields.next()?.parse().ok()?;
+    let nanos = fields.next()?.parse().ok()?;
+
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some(((day, phase), Duration::from_nanos(nanos)))
+}
+
+/// Benchmarks of every day and phase, compared against a baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub entries: Vec<Entry>,
+    /// Growth of the median, in percent, counted as a regression
+    pub threshold: f64,
+}
+
+impl Report {
+    pub fn new(
+        results: Vec<(u32, Phase, Stats)>,
+        baseline: Option<&Baseline>,
+        threshold: f64,
+    ) -> Report {
+        let entries = results
+            .into_iter()
+            .map(|(day, phase, stats)| Entry {
+                day,
+                phase,
+                stats,
+                baseline: baseline.and_then(|baseline| baseline.get(day, phase)),
+            })
+            .collect();
+
+        Report { entries, threshold }
+    }
+
+    pub fn regressions(&self) -> Vec<&Entry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.regressed(self.threshold))
+            .collect()
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut md = String::new();
+        writeln!(
+            md,
+            "| Day | Phase | Median | Mean | Min | Std dev | Baseline | Change |"
+        )
+        .unwrap();
+        writeln!(
+            md,
+            "|----:|------:|-------:|-----:|----:|--------:|---------:|-------:|"
+        )
+        .unwrap();
+
+        for entry in &self.entries {
+            let baseline = entry.baseline.map_or("-".to_string(), format_duration);
+            let change = match entry.change() {
+                Some(change) if entry.regressed(self.threshold) => {
+                    format!("**{:+.1}%**", change)
+                }
+                Some(change) => format!("{:+.1}%", change),
+                None => "-".to_string(),
+            };
+
+            writeln!(
+                md,
+                "| {} | {} | {} | {} | {} | {} | {} | {} |",
+                entry.day,
+                entry.phase,
+                format_duration(entry.stats.median),
+                format_duration(entry.stats.mean),
+                format_duration(entry.stats.min),
+                format_duration(entry.stats.std_dev),
+                baseline,
+                change
+            )
+            .unwrap();
+        }
+
+        md
+    }
+
+    pub fn json(&self) -> String {
+        let mut json = format!(
+            "{{\n  \"threshold_percent\": {},\n  \"results\": [",
+            self.threshold
+        );
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+
+            write!(
+                json,
+                "{}\n    {{\"day\": {}, \"phase\": \"{}\", \"samples\": {}, \"iterations\": {}, \
+                 \"median_ns\": {}, \"mean_ns\": {}, \"min_ns\": {}, \"std_dev_ns\": {}, \
+                 \"baseline_median_ns\": {}, \"change_percent\": {}, \"regression\": {}}}",
+                if i == 0 { "" } else { "," },
+                entry.day,
+                entry.phase,
+                entry.stats.samples,
+                entry.stats.iterations,
+                entry.stats.median.as_nanos(),
+                entry.stats.mean.as_nanos(),
+                entry.stats.min.as_nanos(),
+                entry.stats.std_dev.as_nanos(),
+                optional(entry.baseline.map(|b| b.as_nanos().to_string())),
+                optional(entry.change().map(|c| format!("{:.2}", c))),
+                entry.regressed(self.threshold)
+            )
+            .unwrap();
+        }
+
+        json.push_str("\n  ]\n}\n");
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(median_ms: u64) -> Stats {
+        let median = Duration::from_millis(median_ms);
+        Stats {
+            samples: 10,
+            iterations: 3,
+            median,
+            mean: median,
+            min: median,
+            std_dev: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("advent2021-baseline-{}.txt", std::process::id()));
+
+        let report = Report::new(
+            vec![(1, Phase::Parse, stats(1)), (1, Phase::Part2, stats(3))],
+            None,
+            10.0,
+        );
+        Baseline::from_entries(&report.entries).save(&path).unwrap();
+
+        let baseline = Baseline::load(&path).unwrap().unwrap();
+        assert_eq!(
+            baseline.get(1, Phase::Parse),
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(
+            baseline.get(1, Phase::Part2),
+            Some(Duration::from_millis(3))
+        );
+        assert_eq!(baseline.get(1, Phase::Part1), None);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), None);
+
+        assert_eq!(
+            Baseline::parse("1 1").unwrap_err(),
+            "line 1: expected `DAY PHASE NANOSECONDS`"
+        );
+        assert!(Baseline::parse("1 3 100").is_err());
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = Baseline::parse("1 1 10000000\n1 2 10000000\n").unwrap();
+        let report = Report::new(
+            vec![
+                (1, Phase::Part1, stats(11)),
+                (1, Phase::Part2, stats(12)),
+                (2, Phase::Part1, stats(50)),
+            ],
+            Some(&baseline),
+            25.0,
+        );
+
+        assert!(report.regressions().is_empty());
+
+        let report = Report {
+            threshold: 10.0,
+            ..report
+        };
+        let regressions = report.regressions();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(
+            (regressions[0].day, regressions[0].phase),
+            (1, Phase::Part2)
+        );
+        assert!((regressions[0].change().unwrap() - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn render() {
+        let baseline = Baseline::parse("1 1 10000000").unwrap();
+        let report = Report::new(
+            vec![(1, Phase::Part1, stats(12)), (1, Phase::Parse, stats(3))],
+            Some(&baseline),
+            10.0,
+        );
+
+        let md = report.markdown();
+        let rows: Vec<_> = md.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[2],
+            "| 1 | 1 | 12.0 ms | 12.0 ms | 12.0 ms | 0.00 ns | 10.0 ms | **+20.0%** |"
+        );
+        assert_eq!(
+            rows[3],
+            "| 1 | parse | 3.00 ms | 3.00 ms | 3.00 ms | 0.00 ns | - | - |"
+        );
+
+        let json = report.json();
+        assert!(json.starts_with("{\n  \"threshold_percent\": 10,\n  \"results\": [\n"));
+        assert!(json.contains(
+            "{\"day\": 1, \"phase\": \"1\", \"samples\": 10, \"iterations\": 3, \
+             \"median_ns\": 12000000, \"mean_ns\": 12000000, \"min_ns\": 12000000, \
+             \"std_dev_ns\": 0, \"baseline_median_ns\": 10000000, \
+             \"change_percent\": 20.00, \"regression\": true},\n"
+        ));
+        assert!(json.contains("\"baseline_median_ns\": null, \"change_percent\": null"));
+        assert!(json.ends_with("}\n  ]\n}\n"));
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Module every new day starts from, with `Day0` standing in for its name
+const TEMPLATE: &str = include_str!("days/day_template.rs");
+
+/// Source of a new day's module
+pub fn render_template(day: u32) -> String {
+    TEMPLATE
+        .replace(
+            "adventofcode.com/2021/day/?",
+            &format!("adventofcode.com/2021/day/{}", day),
+        )
+        .replace("const DAY: u32 = 0;", &format!("const DAY: u32 = {};", day))
+        .replace("Day0", &format!("Day{}", day))
+}
+
+fn already_exists(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::AlreadyExists, message)
+}
+
+/// Add a day's `mod` declaration and registry entry to `days/mod.rs`
+///
+/// Declarations are kept in the lexicographic order rustfmt sorts them in,
+/// registry entries in day order.
+pub fn register(mod_rs: &str, day: u32) -> io::Result<String> {
+    let name = format!("day{}", day);
+    let declaration = format!("pub mod {};", name);
+    let entry = format!("Puzzle::of::<{}::Day{}>(),", name, day);
+
+    let lines: Vec<&str> = mod_rs.lines().collect();
+    if lines.iter().any(|line| line.trim() == declaration) {
+        return Err(already_exists(format!("{} is already declared", name)));
+    }
+
+    let declared = |line: &str| {
+        line.strip_prefix("pub mod day")
+            .and_then(|rest| rest.strip_suffix(';'))
+            .map(|number| format!("day{}", number))
+    };
+    let registered = |line: &str| {
+        line.trim()
+            .strip_prefix("Puzzle::of::<day")
+            .and_then(|rest| rest.split_once("::"))
+            .and_then(|(number, _)| number.parse::<u32>().ok())
+    };
+
+    let last_declaration = lines
+        .iter()
+        .rposition(|line| declared(line).is_some())
+        .ok_or_else(|| invalid("no `pub mod dayN;` declarations found"))?;
+    let declaration_at = lines
+        .iter()
+        .position(|line| declared(line).is_some_and(|other| other > name))
+        .unwrap_or(last_declaration + 1);
+
+    let last_entry = lines
+        .iter()
+        .rposition(|line| registered(line).is_some())
+        .ok_or_else(|| invalid("no registry entries found"))?;
+    let entry_at = lines
+        .iter()
+        .position(|line| registered(line).is_some_and(|other| other > day))
+        .unwrap_or(last_entry + 1);
+    let indent =
+        &lines[last_entry][..lines[last_entry].len() - lines[last_entry].trim_start().len()];
+
+    let mut out = String::with_capacity(mod_rs.len() + 64);
+    for (i, line) in lines.iter().enumerate() {
+        if i == declaration_at {
+            out.push_str(&declaration);
+            out.push('\n');
+        }
+        if i == entry_at {
+            out.push_str(indent);
+            out.push_str(&entry);
+            out.push('\n');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("days/mod.rs: {}", message),
+    )
+}
+
+/// Create the module of a new day under `src_dir`, register it and create its
+/// empty example and real input under `data_dir`
+///
+/// Nothing is written when the module, its registration or either input
+/// already exists. Returns the created files.
+pub fn new_day(src_dir: &Path, data_dir: &Path, day: u32) -> io::Result<Vec<PathBuf>> {
+    let module = src_dir.join(format!("days/day{}.rs", day));
+    let mod_rs = src_dir.join("days/mod.rs");
+    let example = data_dir.join(format!("test/day{}/1.txt", day));
+    let real = data_dir.join(format!("real/day{}.txt", day));
+
+    for path in [&module, &example, &real] {
+        if path.exists() {
+            return Err(already_exists(format!("{} already exists", path.display())));
+        }
+    }
+
+    let registered = fs::read_to_string(&mod_rs)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", mod_rs.display(), e)))?;
+    let registered = register(&registered, day)?;
+
+    fs::write(&module, render_template(day))?;
+    fs::write(&mod_rs, registered)?;
+    for path in [&example, &real] {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, "")?;
+    }
+
+    Ok(vec![module, mod_rs, example, real])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    const MOD_RS: &str = "\
+pub mod day1;
+pub mod day10;
+pub mod day2;
+
+use crate::Solution;
+
+pub static REGISTRY: &[Puzzle] = &[
+    Puzzle::of::<day1::Day1>(),
+    Puzzle::of::<day2::Day2>(),
+    Puzzle::of::<day10::Day10>(),
+];
+";
+
+    #[test]
+    fn template() {
+        let source = render_template(15);
+        assert!(source.starts_with("// https://adventofcode.com/2021/day/15\n"));
+        assert!(source.contains("pub struct Day15;"));
+        assert!(source.contains("const DAY: u32 = 15;"));
+        assert!(source.contains("Day15::solve_part2"));
+        assert!(!source.contains("Day0"));
+    }
+
+    #[test]
+    fn register_in_order() {
+        let registered = register(MOD_RS, 3).unwrap();
+        assert!(
+            registered.starts_with("pub mod day1;\npub mod day10;\npub mod day2;\npub mod day3;\n")
+        );
+        assert!(registered.contains(
+            "    Puzzle::of::<day2::Day2>(),\n    Puzzle::of::<day3::Day3>(),\n    Puzzle::of::<day10::Day10>(),\n"
+        ));
+
+        let registered = register(MOD_RS, 11).unwrap();
+        assert!(registered.contains("pub mod day10;\npub mod day11;\npub mod day2;\n"));
+        assert!(registered.contains("    Puzzle::of::<day11::Day11>(),\n];\n"));
+
+        let registered = register(MOD_RS, 25).unwrap();
+        assert!(registered.contains("pub mod day2;\npub mod day25;\n\nuse"));
+
+        let error = register(MOD_RS, 2).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn scaffold_new_day() {
+        let root = env::temp_dir().join(format!("advent2021-scaffold-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (src, data) = (root.join("src"), root.join("data"));
+        fs::create_dir_all(src.join("days")).unwrap();
+        fs::write(src.join("days/mod.rs"), MOD_RS).unwrap();
+
+        let created = new_day(&src, &data, 3).unwrap();
+        assert_eq!(created.len(), 4);
+        assert_eq!(
+            fs::read_to_string(src.join("days/day3.rs")).unwrap(),
+            render_template(3)
+        );
+        assert!(fs::read_to_string(src.join("days/mod.rs"))
+            .unwrap()
+            .contains("pub mod day3;"));
+        assert_eq!(
+            fs::read_to_string(data.join("test/day3/1.txt")).unwrap(),
+            ""
+        );
+        assert_eq!(fs::read_to_string(data.join("real/day3.txt")).unwrap(), "");
+
+        // A second run leaves everything as it was
+        fs::write(src.join("days/day3.rs"), "edited").unwrap();
+        let error = new_day(&src, &data, 3).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(
+            fs::read_to_string(src.join("days/day3.rs")).unwrap(),
+            "edited"
+        );
+
+        // Only an input of the day exists
+        fs::write(data.join("real/day4.txt"), "1\n").unwrap();
+        let error = new_day(&src, &data, 4).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert!(!src.join("days/day4.rs").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn register_in_days_mod() {
+        let mod_rs = include_str!("days/mod.rs");
+        let registered = register(mod_rs, 25).unwrap();
+        assert!(registered.contains("    Puzzle::of::<day25::Day25>(),\n];"));
+    }
+}
@@ -0,0 +1,517 @@
+use crate::{
+    bench::format_duration,
+    days::{self, REGISTRY},
+    executor::catch_panic,
+    normalize,
+    verify::manifest_form,
+    ParseError,
+};
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Largest request body accepted when none is given, well above any real
+/// input
+pub const DEFAULT_MAX_BODY: usize = 1 << 20;
+
+/// Longest a part may take to solve when no limit is given
+pub const DEFAULT_SOLVE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Requests handled at once, later ones are turned away until one finishes
+pub const MAX_CONNECTIONS: usize = 64;
+
+/// Longest request line and headers taken together
+const MAX_HEAD: u64 = 8 * 1024;
+
+/// How long a client may take to send its request
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON service solving puzzle inputs posted to it over HTTP
+///
+/// `GET /days` lists the implemented days and
+/// `POST /days/DAY/parts/PART` solves the input in the request body. Each
+/// connection is handled on its own thread and carries a single request.
+/// A solve running past its timeout is answered with a 504 and left to
+/// finish in the background, so its connection is freed either way.
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    max_body: usize,
+    timeout: Duration,
+}
+
+impl Server {
+    pub fn bind(
+        addr: impl ToSocketAddrs,
+        max_body: usize,
+        timeout: Duration,
+    ) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            max_body,
+            timeout,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept and answer requests until the listener fails
+    pub fn run(self) -> io::Result<()> {
+        let open = Arc::new(AtomicUsize::new(0));
+
+        for stream in self.listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                // The client gave up before it was accepted
+                Err(e) if e.kind() == io::ErrorKind::ConnectionAborted => continue,
+                Err(e) => return Err(e),
+            };
+
+            if open.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                open.fetch_sub(1, Ordering::SeqCst);
+                let _ = write_response(&mut stream, 503, &error_json("too many requests"));
+                continue;
+            }
+
+            let open = Arc::clone(&open);
+            let (max_body, timeout) = (self.max_body, self.timeout);
+            thread::spawn(move || {
+                // Nothing is left to tell a client whose connection failed
+                let _ = handle(stream, max_body, timeout);
+                open.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Request line and body of an HTTP request
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+/// Read one request and answer it
+fn handle(stream: TcpStream, max_body: usize, timeout: Duration) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream);
+    let request = read_request(&mut reader, max_body);
+    let mut stream = reader.into_inner();
+
+    match request {
+        Ok(request) => {
+            let (status, body) = respond(&request.method, &request.path, &request.body, timeout);
+            write_response(&mut stream, status, &body)
+        }
+        Err((status, message)) => {
+            write_response(&mut stream, status, &error_json(&message))?;
+
+            // Closing with part of a rejected request still unread would
+            // reset the connection, and the client could lose the response
+            stream.shutdown(Shutdown::Write)?;
+            io::copy(&mut (&stream).take(max_body as u64), &mut io::sink())?;
+            Ok(())
+        }
+    }
+}
+
+/// Read the request line, headers and body, or the status and reason to
+/// reject the request with
+fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, (u16, String)> {
+    let bad = |message: &str| (400, message.to_string());
+    let mut head = (&mut *reader).take(MAX_HEAD);
+
+    let mut line = String::new();
+    head.read_line(&mut line)
+        .map_err(|_| bad("malformed request"))?;
+    let mut fields = line.split_whitespace();
+    let (method, target) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/") => {
+            (method.to_string(), target.to_string())
+        }
+        _ => return Err(bad("malformed request line")),
+    };
+
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        if head
+            .read_line(&mut line)
+            .map_err(|_| bad("malformed headers"))?
+            == 0
+        {
+            return match head.limit() {
+                0 => Err((431, "request headers are too large".to_string())),
+                _ => Err(bad("the request ended in its headers")),
+            };
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| bad("malformed header"))?;
+
+        if name.eq_ignore_ascii_case("Content-Length") {
+            length = value
+                .trim()
+                .parse()
+                .map_err(|_| bad("malformed content length"))?;
+        } else if name.eq_ignore_ascii_case("Transfer-Encoding") {
+            return Err((411, "the body needs a content length".to_string()));
+        }
+    }
+
+    if length > max_body {
+        return Err((
+            413,
+            format!("the body is over the limit of {} bytes", max_body),
+        ));
+    }
+
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad("the body is shorter than its content length"))?;
+    let body = String::from_utf8(body).map_err(|_| bad("the body is not UTF-8"))?;
+
+    // The query string is not used
+    let path = target.split('?').next().unwrap().to_string();
+    Ok(Request { method, path, body })
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Status and JSON body answering a request, a solve taking at most
+/// `timeout`
+fn respond(method: &str, path: &str, body: &str, timeout: Duration) -> (u16, String) {
+    let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+
+    match segments.as_slice() {
+        ["days"] if method == "GET" => (200, days_json()),
+        ["days", day, "parts", part] if method == "POST" => match (day.parse(), part.parse()) {
+            (Ok(day), Ok(part @ (1 | 2))) if days::get(day).is_some() => {
+                solve(day, part, body, timeout)
+            }
+            _ => (404, error_json("no such day or part")),
+        },
+        ["days"] | ["days", _, "parts", _] => (405, error_json("method not allowed")),
+        _ => (404, error_json("not found")),
+    }
+}
+
+fn days_json() -> String {
+    let days: Vec<_> = REGISTRY
+        .iter()
+        .map(|puzzle| {
+            format!(
+                "{{\"day\": {}, \"title\": {}}}",
+                puzzle.day,
+                json_string(puzzle.title)
+            )
+        })
+        .collect();
+
+    format!("{{\"days\": [{}]}}", days.join(", "))
+}
+
+/// Solve one part of an input, timing the parse and the part apart
+fn solve(day: u32, part: u32, input: &str, timeout: Duration) -> (u16, String) {
+    let puzzle = days::get(day).unwrap();
+    let input = normalize(input);
+
+    let solved = with_deadline(timeout, move || {
+        let start = Instant::now();
+        let parsed = puzzle.parse(&input)?;
+        let parse_time = start.elapsed();
+
+        let start = Instant::now();
+        let solution = puzzle.run(part, &parsed).unwrap();
+        Ok::<_, ParseError>((solution, parse_time, start.elapsed()))
+    });
+    let Some(solved) = solved else {
+        let message = format!(
+            "the solver did not finish within {}",
+            format_duration(timeout)
+        );
+        return (504, error_json(&message));
+    };
+
+    match solved {
+        Ok(Ok((solution, parse_time, solve_time))) => {
+            let answer = match &solution {
+                Ok(_) => json_string(&manifest_form(&solution)),
+                Err(_) => "null".to_string(),
+            };
+            let json = format!(
+                "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}",
+                day,
+                part,
+                answer,
+                parse_time.as_nanos(),
+                solve_time.as_nanos()
+            );
+            (200, json)
+        }
+        Ok(Err(e)) => (422, parse_error_json(&e)),
+        Err(panic) => (500, error_json(&format!("the solver panicked: {}", panic))),
+    }
+}
+
+/// Run `f` on a thread of its own and wait for it up to `timeout`, its panic
+/// turned into its message
+///
+/// `None` when `f` did not finish in time. A thread cannot be stopped, so it
+/// keeps running and its result is dropped.
+fn with_deadline<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Option<Result<T, String>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // Nobody listens any more once the deadline has passed
+        let _ = sender.send(catch_panic(f));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => None,
+        Err(RecvTimeoutError::Disconnected) => Some(Err("the solver stopped".to_string())),
+    }
+}
+
+fn error_json(message: &str) -> String {
+    format!("{{\"error\": {}}}", json_string(message))
+}
+
+fn parse_error_json(e: &ParseError) -> String {
+    format!(
+        "{{\"error\": {}, \"parse_error\": {{\"line\": {}, \"column\": {}, \"text\": {}, \
+         \"message\": {}}}}}",
+        json_string(&e.to_string()),
+        e.line,
+        e.column,
+        json_string(&e.text),
+        json_string(&e.message)
+    )
+}
+
+/// Quoted JSON string
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http;
+
+    #[test]
+    fn routes() {
+        let (status, body) = respond("GET", "/days", "", DEFAULT_SOLVE_TIMEOUT);
+        assert_eq!(status, 200);
+        assert!(body.starts_with("{\"days\": [{\"day\": 1, \"title\": \"Sonar Sweep\"}, "));
+
+        let (status, body) = respond(
+            "POST",
+            "/days/1/parts/2",
+            "199\n200\n208\n210\n200\n",
+            DEFAULT_SOLVE_TIMEOUT,
+        );
+        assert_eq!(status, 200);
+        assert!(body.starts_with("{\"day\": 1, \"part\": 2, \"answer\": \"1\", \"parse_ns\": "));
+
+        assert_eq!(
+            respond("POST", "/days/25/parts/1", "", DEFAULT_SOLVE_TIMEOUT).0,
+            404
+        );
+        assert_eq!(
+            respond("POST", "/days/1/parts/3", "", DEFAULT_SOLVE_TIMEOUT).0,
+            404
+        );
+        assert_eq!(
+            respond("GET", "/days/1/parts/1", "", DEFAULT_SOLVE_TIMEOUT).0,
+            405
+        );
+        assert_eq!(respond("GET", "/", "", DEFAULT_SOLVE_TIMEOUT).0, 404);
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let (status, body) = respond(
+            "POST",
+            "/days/1/parts/1",
+            "1\r\n2\r\nthree\r\n",
+            DEFAULT_SOLVE_TIMEOUT,
+        );
+        assert_eq!(status, 422);
+        assert_eq!(
+            body,
+            "{\"error\": \"day 1, line 3, column 1: expected a number (found `three`)\", \
+             \"parse_error\": {\"line\": 3, \"column\": 1, \"text\": \"three\", \
+             \"message\": \"expected a number\"}}"
+        );
+    }
+
+    #[test]
+    fn solve_timeout() {
+        assert_eq!(
+            with_deadline(Duration::from_secs(10), || 1 + 1),
+            Some(Ok(2))
+        );
+        assert_eq!(
+            with_deadline(Duration::from_millis(10), || thread::sleep(TIMEOUT)),
+            None
+        );
+
+        let input = "1\n".repeat(100_000);
+        let (status, body) = respond("POST", "/days/1/parts/1", &input, Duration::ZERO);
+        assert_eq!(status, 504);
+        assert_eq!(
+            body,
+            "{\"error\": \"the solver did not finish within 0.00 ns\"}"
+        );
+    }
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(
+            json_string("a\"b\\c\nd\u{1}é"),
+            "\"a\\\"b\\\\c\\nd\\u0001é\""
+        );
+    }
+
+    #[test]
+    fn read_requests() {
+        let read = |raw: &str| read_request(&mut raw.as_bytes(), 8);
+
+        assert_eq!(
+            read("POST /days/1/parts/1?x=1 HTTP/1.1\r\nContent-Length: 4\r\n\r\n1\n2\n"),
+            Ok(Request {
+                method: "POST".to_string(),
+                path: "/days/1/parts/1".to_string(),
+                body: "1\n2\n".to_string()
+            })
+        );
+        assert_eq!(read("GET /days HTTP/1.1\r\n\r\n").unwrap().body, "");
+        assert_eq!(read("nonsense\r\n\r\n").unwrap_err().0, 400);
+        assert_eq!(
+            read("POST / HTTP/1.1\r\nContent-Length: 9\r\n\r\n123456789").unwrap_err(),
+            (413, "the body is over the limit of 8 bytes".to_string())
+        );
+        assert_eq!(
+            read("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n")
+                .unwrap_err()
+                .0,
+            411
+        );
+        let long_header = format!("GET / HTTP/1.1\r\nX: {}\r\n\r\n", "a".repeat(9000));
+        assert_eq!(read(&long_header).unwrap_err().0, 431);
+    }
+
+    #[test]
+    fn serves_concurrent_requests() {
+        let server = Server::bind("127.0.0.1:0", 64, DEFAULT_SOLVE_TIMEOUT).unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        thread::spawn(move || server.run());
+
+        let clients: Vec<_> = (0..8)
+            .map(|i| {
+                let url = url.clone();
+                thread::spawn(move || {
+                    let input = format!("{}\n{}\n", i, i + 1);
+                    http::request(
+                        "POST",
+                        &format!("{}/days/1/parts/1", url),
+                        &[],
+                        Some(&input),
+                    )
+                    .unwrap()
+                })
+            })
+            .collect();
+        for client in clients {
+            let response = client.join().unwrap();
+            assert_eq!(response.status, 200);
+            assert_eq!(response.header("Content-Type"), Some("application/json"));
+            assert!(response.body.contains("\"answer\": \"1\""));
+        }
+
+        let response = http::request("GET", &format!("{}/days", url), &[], None).unwrap();
+        assert_eq!(response.status, 200);
+
+        let too_large = "1\n".repeat(100);
+        let response = http::request(
+            "POST",
+            &format!("{}/days/1/parts/1", url),
+            &[],
+            Some(&too_large),
+        )
+        .unwrap();
+        assert_eq!(response.status, 413);
+        assert_eq!(
+            response.body,
+            "{\"error\": \"the body is over the limit of 64 bytes\"}"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{ParseError, Solution, Source};
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+};
+
+/// Error from solving a puzzle input read from a stream
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> StreamError {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> StreamError {
+        StreamError::Parse(e)
+    }
+}
+
+/// Part of a puzzle solved one parsed line at a time
+///
+/// Both the string and the streaming API of a day push their lines through
+/// the same fold, so they cannot disagree.
+pub trait Fold<T>: Default {
+    fn push(&mut self, item: &T);
+    fn finish(self) -> Solution;
+
+    /// Fold every item of an already parsed input
+    fn fold<'a>(items: impl IntoIterator<Item = &'a T>) -> Solution
+    where
+        T: 'a,
+    {
+        let mut fold = Self::default();
+        for item in items {
+            fold.push(item);
+        }
+        fold.finish()
+    }
+}
+
+/// Parse every line of `reader` with `parse_line` and push it into a new
+/// fold, holding a single line in memory at a time
+///
+/// Lines are split like [`str::lines`] splits them, so a line fails to parse
+/// exactly where it would in the whole input. The fold is returned unfinished
+/// for days that check the input as a whole afterwards.
+pub fn fold_lines<T, F: Fold<T>>(
+    day: u32,
+    mut reader: impl BufRead,
+    mut parse_line: impl for<'a> FnMut(&Source<'a>, &'a str) -> Result<T, ParseError>,
+) -> Result<F, StreamError> {
+    let mut fold = F::default();
+    let mut buffer = String::new();
+    let mut line_number = 0;
+
+    loop {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            return Ok(fold);
+        }
+        line_number += 1;
+
+        let line = match buffer.strip_suffix('\n') {
+            Some(line) => line.strip_suffix('\r').unwrap_or(line),
+            None => &buffer,
+        };
+
+        let source = Source::at_line(day, line, line_number);
+        fold.push(&parse_line(&source, line)?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    /// Sum of the numbers on every line
+    #[derive(Default)]
+    struct Sum(i64);
+
+    impl Fold<i64> for Sum {
+        fn push(&mut self, item: &i64) {
+            self.0 += item;
+        }
+
+        fn finish(self) -> Solution {
+            Ok(self.0.into())
+        }
+    }
+
+    fn sum(input: &[u8]) -> Result<Solution, StreamError> {
+        fold_lines(0, input, |source, line| source.number(line)).map(Sum::finish)
+    }
+
+    #[test]
+    fn fold_stream() {
+        assert_eq!(sum(b"1\n2\r\n3").unwrap(), Ok(6.into()));
+        assert_eq!(sum(b"").unwrap(), Ok(0.into()));
+        assert_eq!(Sum::fold(&[4, 5]), Ok(9.into()));
+
+        let error = match sum(b"1\n2\n\n4\n") {
+            Err(StreamError::Parse(e)) => e,
+            other => panic!("expected a parse error, found {:?}", other),
+        };
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.message, "expected a number");
+    }
+
+    /// The large inputs streaming is for, whose answers overflow 32 bits
+    #[test]
+    fn stream_large_generated_inputs() {
+        use crate::{days, generate};
+
+        let input = generate::get(2).unwrap().generate(2_000_000, 0);
+        let part1 = days::day2::part1_reader(input.as_bytes()).unwrap();
+        let part2 = days::day2::part2_reader(input.as_bytes()).unwrap();
+        assert!(part1.is_ok() && part2.is_ok());
+        assert_eq!(part2, days::day2::part2(&input).unwrap());
+
+        let input = generate::get(3).unwrap().generate(100_000, 0);
+        let part1 = days::day3::part1_reader(input.as_bytes()).unwrap();
+        let part2 = days::day3::part2_reader(input.as_bytes()).unwrap();
+        assert!(part1.is_ok() && part2.is_ok());
+        assert_eq!(part1, days::day3::part1(&input).unwrap());
+    }
+
+    #[test]
+    fn stream_read_error() {
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken pipe"))
+            }
+        }
+
+        let result: Result<Sum, _> = fold_lines(0, io::BufReader::new(Broken), |source, line| {
+            source.number(line)
+        });
+        assert!(matches!(result, Err(StreamError::Io(_))));
+    }
+}
@@ -0,0 +1,587 @@
+use crate::{
+    download::{DEFAULT_BASE_URL, DEFAULT_REQUEST_DELAY, STAMP_FILE, YEAR},
+    http::{self, Throttle},
+    Answer, Config,
+};
+use std::{
+    error::Error,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// File in the data directory holding every submitted guess
+pub const GUESS_LOG_FILE: &str = "guesses.txt";
+
+/// How the website judged a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint of the direction
+    Wrong,
+    /// Submitted too soon after the previous answer, with the time left to
+    /// wait when the page gives it
+    RateLimited(Option<Duration>),
+    /// The part was already solved, the answer was not checked
+    AlreadySolved,
+    /// A page that could not be interpreted, with its message
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Read the verdict from the page the website answers a submission with
+    pub fn classify(page: &str) -> Verdict {
+        let message = page_message(page);
+        let lower = message.to_ascii_lowercase();
+
+        if lower.contains("answer too recently") {
+            Verdict::RateLimited(parse_wait(&lower))
+        } else if lower.contains("did you already complete it") {
+            Verdict::AlreadySolved
+        } else if lower.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if lower.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if lower.contains("not the right answer") {
+            Verdict::Wrong
+        } else if lower.contains("the right answer") {
+            Verdict::Correct
+        } else {
+            Verdict::Unknown(message)
+        }
+    }
+
+    /// Whether the website checked the answer and found it wrong
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited(_) => "rate-limited",
+            Verdict::AlreadySolved => "already-solved",
+            Verdict::Unknown(_) => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        Some(match name {
+            "correct" => Verdict::Correct,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "rate-limited" => Verdict::RateLimited(None),
+            "already-solved" => Verdict::AlreadySolved,
+            "unknown" => Verdict::Unknown(String::new()),
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(Some(wait)) => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            Verdict::RateLimited(None) => write!(f, "rate limited"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unknown(message) => write!(f, "unrecognized response: {}", message),
+        }
+    }
+}
+
+/// Text of the `<article>` holding the message, without its tags
+fn page_message(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Read a wait such as "you have 1m 34s left to wait"
+fn parse_wait(message: &str) -> Option<Duration> {
+    let start = message.rfind("you have ")? + "you have ".len();
+    let end = start + message[start..].find(" left to wait")?;
+
+    message[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (value, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// One submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub day: u32,
+    pub part: u32,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Why a guess was not sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The same answer was already judged wrong
+    AlreadyRejected,
+    /// The answer is not below a guess that was too high
+    AboveTooHigh(i64),
+    /// The answer is not above a guess that was too low
+    BelowTooLow(i64),
+    /// The part was already solved with this answer
+    AlreadySolved(String),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadyRejected => write!(f, "this answer was already rejected"),
+            Refusal::AboveTooHigh(bound) => write!(f, "{} was already too high", bound),
+            Refusal::BelowTooLow(bound) => write!(f, "{} was already too low", bound),
+            Refusal::AlreadySolved(answer) => write!(f, "already solved with {}", answer),
+        }
+    }
+}
+
+/// Local record of every guess, kept as one `day part verdict answer` line
+/// per guess
+#[derive(Debug, Clone)]
+pub struct GuessLog {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl GuessLog {
+    /// Load the log at `path`, which is empty when the file does not exist
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<GuessLog> {
+        let path = path.into();
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("{}: {}", path.display(), e),
+                ))
+            }
+        };
+
+        let guesses = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                parse_guess(line).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}: line {}: malformed guess", path.display(), i + 1),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(GuessLog { path, guesses })
+    }
+
+    /// Load the log kept in the data directory
+    pub fn open() -> io::Result<GuessLog> {
+        GuessLog::load(crate::data_dir()?.join(GUESS_LOG_FILE))
+    }
+
+    pub fn guesses(&self) -> &[Guess] {
+        &self.guesses
+    }
+
+    /// Check a guess against the earlier ones for the same part
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Result<(), Refusal> {
+        let value: Option<i64> = answer.parse().ok();
+
+        for guess in self
+            .guesses
+            .iter()
+            .filter(|guess| guess.day == day && guess.part == part)
+        {
+            if guess.verdict == Verdict::Correct {
+                return Err(Refusal::AlreadySolved(guess.answer.clone()));
+            }
+            if guess.verdict.is_rejection() && guess.answer == answer {
+                return Err(Refusal::AlreadyRejected);
+            }
+
+            let bound = guess.answer.parse::<i64>().ok();
+            match (&guess.verdict, value, bound) {
+                (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    return Err(Refusal::AboveTooHigh(bound))
+                }
+                (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    return Err(Refusal::BelowTooLow(bound))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add a guess to the log and append it to the file
+    pub fn record(&mut self, guess: Guess) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            file,
+            "{} {} {} {}",
+            guess.day,
+            guess.part,
+            guess.verdict.name(),
+            guess.answer
+        )?;
+
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+fn parse_guess(line: &str) -> Option<Guess> {
+    let mut fields = line.splitn(4, ' ');
+
+    Some(Guess {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        verdict: Verdict::from_name(fields.next()?)?,
+        answer: fields.next()?.to_string(),
+    })
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    /// No session token in the config
+    MissingSession,
+    /// The answer is glyphs that could not be read as letters
+    Unreadable,
+    /// The answer is empty, which the website would only count as a wrong
+    /// guess
+    Empty,
+    /// The guess log rules the answer out
+    Refused(Refusal),
+    /// The website answered with something other than success
+    Status(u16, String),
+    Io(io::Error),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::MissingSession => write!(f, "no `session` set in the config file"),
+            SubmitError::Unreadable => write!(f, "the answer could not be read as letters"),
+            SubmitError::Empty => write!(f, "the answer is empty"),
+            SubmitError::Refused(refusal) => write!(f, "not submitting: {}", refusal),
+            SubmitError::Status(status, message) => {
+                write!(f, "server answered {}: {}", status, message)
+            }
+            SubmitError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+impl From<io::Error> for SubmitError {
+    fn from(e: io::Error) -> SubmitError {
+        SubmitError::Io(e)
+    }
+}
+
+/// Percent-encode a form value
+fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Posts answers to the website
+#[derive(Debug, Clone)]
+pub struct Submitter {
+    base_url: String,
+    session: String,
+    throttle: Throttle,
+}
+
+impl Submitter {
+    pub fn new(
+        base_url: impl Into<String>,
+        session: impl Into<String>,
+        throttle: Throttle,
+    ) -> Self {
+        Submitter {
+            base_url: base_url.into(),
+            session: session.into(),
+            throttle,
+        }
+    }
+
+    /// Build a submitter from the config, throttled through the same stamp
+    /// file as the downloader
+    pub fn from_config(config: &Config) -> Result<Submitter, SubmitError> {
+        let session = config.session.clone().ok_or(SubmitError::MissingSession)?;
+        let base_url = config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        http::check_client(&base_url)?;
+        let delay = config.request_delay.unwrap_or(DEFAULT_REQUEST_DELAY);
+
+        let stamp = crate::data_dir()?.join(STAMP_FILE);
+
+        Ok(Submitter::new(
+            base_url,
+            session,
+            Throttle::new(stamp, delay),
+        ))
+    }
+
+    /// Post an answer without consulting the guess log
+    pub fn post(&self, day: u32, part: u32, answer: &str) -> Result<Verdict, SubmitError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let cookie = format!("session={}", self.session);
+        let form = format!("level={}&answer={}", part, form_encode(answer));
+
+        self.throttle.wait()?;
+        let response = http::request(
+            "POST",
+            &url,
+            &[
+                ("Cookie", &cookie),
+                ("Content-Type", "application/x-www-form-urlencoded"),
+            ],
+            Some(&form),
+        )?;
+
+        if response.status != 200 {
+            let message = response.body.lines().next().unwrap_or("").trim();
+            return Err(SubmitError::Status(response.status, message.to_string()));
+        }
+
+        Ok(Verdict::classify(&response.body))
+    }
+
+    /// Submit an answer unless it is empty or the log rules it out, then
+    /// record the verdict
+    pub fn submit(
+        &self,
+        log: &mut GuessLog,
+        day: u32,
+        part: u32,
+        answer: &Answer,
+    ) -> Result<Verdict, SubmitError> {
+        let answer = answer.submission().ok_or(SubmitError::Unreadable)?;
+        if answer.trim().is_empty() {
+            return Err(SubmitError::Empty);
+        }
+        log.check(day, part, &answer)
+            .map_err(SubmitError::Refused)?;
+
+        let verdict = self.post(day, part, &answer)?;
+        log.record(Guess {
+            day,
+            part,
+            verdict: verdict.clone(),
+            answer,
+        })?;
+
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test_server;
+    use std::{env, process};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("advent2021-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn page(message: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>\n",
+            message
+        )
+    }
+
+    #[test]
+    fn classify_pages() {
+        let cases = [
+            (
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.  If you're stuck, ...",
+                Verdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, ...",
+                Verdict::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 34s left to wait.",
+                Verdict::RateLimited(Some(Duration::from_secs(94))),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Verdict::AlreadySolved,
+            ),
+        ];
+
+        for (message, verdict) in cases {
+            assert_eq!(Verdict::classify(&page(message)), verdict, "{}", message);
+        }
+
+        assert_eq!(
+            Verdict::classify(&page("Something <em>else</em>")),
+            Verdict::Unknown("Something else".to_string())
+        );
+    }
+
+    #[test]
+    fn log_refusals() {
+        let dir = temp_dir("guesses");
+        let path = dir.join(GUESS_LOG_FILE);
+
+        let mut log = GuessLog::load(&path).unwrap();
+        for (verdict, answer) in [(Verdict::TooHigh, "500"), (Verdict::TooLow, "100")] {
+            log.record(Guess {
+                day: 3,
+                part: 1,
+                verdict,
+                answer: answer.to_string(),
+            })
+            .unwrap();
+        }
+
+        let log = GuessLog::load(&path).unwrap();
+        assert_eq!(log.guesses().len(), 2);
+        assert_eq!(log.check(3, 1, "500"), Err(Refusal::AlreadyRejected));
+        assert_eq!(log.check(3, 1, "600"), Err(Refusal::AboveTooHigh(500)));
+        assert_eq!(log.check(3, 1, "50"), Err(Refusal::BelowTooLow(100)));
+        assert_eq!(log.check(3, 1, "250"), Ok(()));
+        assert_eq!(log.check(3, 2, "600"), Ok(()));
+
+        fs::write(&path, "3 1 maybe 12\n").unwrap();
+        assert!(GuessLog::load(&path).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn submit_answers() {
+        let dir = temp_dir("submit");
+        let server = test_server::serve(vec![
+            (
+                200,
+                page("That's not the right answer; your answer is too low."),
+            ),
+            (200, page("That's the right answer!")),
+        ]);
+        let submitter = Submitter::new(
+            &server.url,
+            "abc123",
+            Throttle::new(dir.join(STAMP_FILE), Duration::ZERO),
+        );
+        let mut log = GuessLog::load(dir.join(GUESS_LOG_FILE)).unwrap();
+
+        let verdict = submitter.submit(&mut log, 7, 2, &Answer::from(41)).unwrap();
+        assert_eq!(verdict, Verdict::TooLow);
+
+        // Ruled out by the log, so no request is made
+        let result = submitter.submit(&mut log, 7, 2, &Answer::from(40));
+        assert!(matches!(
+            result,
+            Err(SubmitError::Refused(Refusal::BelowTooLow(41)))
+        ));
+
+        // Neither sent nor logged
+        let result = submitter.submit(&mut log, 7, 2, &Answer::Text(String::new()));
+        assert!(matches!(result, Err(SubmitError::Empty)));
+
+        let verdict = submitter.submit(&mut log, 7, 2, &Answer::from(42)).unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let received = server.received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].method, "POST");
+        assert_eq!(received[0].path, "/2021/day/7/answer");
+        assert_eq!(received[0].header("Cookie"), Some("session=abc123"));
+        assert_eq!(received[0].body, "level=2&answer=41");
+
+        assert_eq!(
+            fs::read_to_string(dir.join(GUESS_LOG_FILE)).unwrap(),
+            "7 2 too-low 41\n7 2 correct 42\n"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn encode_form_values() {
+        assert_eq!(form_encode("EFJKZLBL"), "EFJKZLBL");
+        assert_eq!(form_encode("a b&c"), "a%20b%26c");
+    }
+}
@@ -0,0 +1,439 @@
+use crate::{ParseError, Source};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// Offsets of the 4 orthogonal neighbours, clockwise from up
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all 8 surrounding neighbours, clockwise from up
+const SURROUNDING: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Rectangular grid stored row by row, addressed by `(x, y)` from the top
+/// left corner
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Grid with each cell set to `f(x, y)`
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Grid of `cells` given row by row
+    ///
+    /// Panics when there are not `width * height` cells.
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parse a map with one row per line, converting each character with
+    /// `cell`
+    ///
+    /// `what` describes a valid character in errors, every row must be as
+    /// long as the first.
+    pub fn parse_map<'a>(
+        source: &Source<'a>,
+        text: &'a str,
+        what: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let lines: Vec<_> = text.lines().collect();
+        let width = source
+            .expect(lines.first().copied(), text, "a row")?
+            .chars()
+            .count();
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in &lines {
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    source.error(&line[i..i + c.len_utf8()], format!("expected {}", what))
+                })?;
+                cells.push(value);
+            }
+
+            if line.chars().count() != width {
+                return Err(source.error(line, format!("expected {} characters", width)));
+            }
+        }
+
+        Ok(Grid::from_cells(width, lines.len(), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        self.contains(x, y).then(|| y * self.width + x)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Set a cell, returning the value it held or `None` when `(x, y)` is
+    /// outside the grid
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> Option<T> {
+        self.get_mut(x, y)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Positions of the orthogonal neighbours inside the grid
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_positions(x, y, &ORTHOGONAL)
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours inside the grid
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_positions(x, y, &SURROUNDING)
+    }
+
+    fn offset_positions(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            self.contains(position.0, position.1).then_some(position)
+        })
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell, row by row
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every cell with its position, row by row
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // A grid without columns has no cells, but `chunks` panics on 0
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells[x..].iter().step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells[x..].iter().step_by(self.width))
+    }
+
+    /// Grid of the same size with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Copy of the grid laid out with `(x, y)` taken from `at(x, y)` of this
+    /// one, in a grid of `width` by `height`
+    fn rearranged(
+        &self,
+        width: usize,
+        height: usize,
+        at: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(width, height, |x, y| {
+            let (x, y) = at(x, y);
+            self[(x, y)].clone()
+        })
+    }
+
+    /// Mirror along the diagonal from the top left corner
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotate a quarter turn clockwise
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotate a quarter turn counterclockwise
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+}
+
+impl Grid<char> {
+    /// Parse a map of arbitrary characters
+    pub fn parse_chars<'a>(source: &Source<'a>, text: &'a str) -> Result<Grid<char>, ParseError> {
+        Grid::parse_map(source, text, "a character", Some)
+    }
+}
+
+impl Grid<u32> {
+    /// Parse a map of decimal digits
+    pub fn parse_digits<'a>(source: &Source<'a>, text: &'a str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse_map(source, text, "a digit", |c| c.to_digit(10))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        match self.index_of(x, y) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "({}, {}) is outside a {}x{} grid",
+                x, y, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        match self.index_of(x, y) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "({}, {}) is outside a {}x{} grid",
+                x, y, self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Every cell printed next to each other, one line per row
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<u32> {
+        // 1 2 3
+        // 4 5 6
+        Grid::from_fn(3, 2, |x, y| (y * 3 + x + 1) as u32)
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut grid = sample();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+
+        assert_eq!(grid.set(1, 1, 0), Some(5));
+        assert_eq!(grid.set(1, 2, 0), None);
+        assert_eq!(grid[(1, 1)], 0);
+
+        grid[(0, 0)] += 10;
+        assert_eq!(grid.row(0), Some(&[11, 2, 3][..]));
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 2) is outside a 3x2 grid")]
+    fn index_out_of_bounds() {
+        let _ = sample()[(0, 2)];
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 4, ());
+
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(
+            grid.neighbours4(1, 1).collect::<Vec<_>>(),
+            [(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+        assert_eq!(
+            grid.neighbours8(2, 3).collect::<Vec<_>>(),
+            [(2, 2), (1, 3), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = sample();
+
+        let rows: Vec<_> = grid.rows().collect();
+        assert_eq!(rows, [&[1, 2, 3][..], &[4, 5, 6][..]]);
+
+        let columns: Vec<Vec<_>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(grid.column(1).unwrap().collect::<Vec<_>>(), [&2, &5]);
+        assert!(grid.column(3).is_none());
+
+        assert_eq!(grid.enumerate().nth(4), Some(((1, 1), &5)));
+        assert_eq!(Grid::<u32>::from_cells(0, 2, vec![]).rows().count(), 0);
+    }
+
+    #[test]
+    fn rearrange() {
+        let grid = sample();
+
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.rotate_right().to_string(), "41\n52\n63\n");
+        assert_eq!(grid.rotate_left().to_string(), "36\n25\n14\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "321\n654\n");
+        assert_eq!(grid.flip_vertical().to_string(), "456\n123\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.map(|n| n % 2 == 0).get(1, 0), Some(&true));
+    }
+
+    #[test]
+    fn parse() {
+        let input = "#.#\n..#\n";
+        let source = Source::new(0, input);
+        let grid = Grid::parse_chars(&source, input).unwrap();
+        assert_eq!(grid.to_string(), input);
+
+        let input = "123\n456\n";
+        let source = Source::new(0, input);
+        assert_eq!(Grid::parse_digits(&source, input).unwrap(), sample());
+
+        let input = "123\n4x6\n";
+        let source = Source::new(0, input);
+        let error = Grid::parse_digits(&source, input).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+
+        let input = "123\n45\n";
+        let source = Source::new(0, input);
+        let error = Grid::parse_digits(&source, input).unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected 3 characters")
+        );
+
+        let source = Source::new(0, "");
+        assert!(Grid::parse_chars(&source, "").is_err());
+    }
+}
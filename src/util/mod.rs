@@ -0,0 +1,208 @@
+mod grid;
+mod parse;
+mod rng;
+
+pub use grid::Grid;
+pub use parse::{ParseError, Source};
+pub use rng::Rng;
+
+use crate::Config;
+use std::{
+    cmp::Ordering,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable overriding the data directory
+pub const DATA_DIR_VAR: &str = "ADVENT_DATA_DIR";
+
+/// Directory holding the `real` and `test` input folders
+///
+/// Taken from `ADVENT_DATA_DIR`, then the config file, then `data` in the
+/// working directory and finally `data` in the crate root.
+pub fn data_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = env::var_os(DATA_DIR_VAR) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    if let Some(dir) = Config::load()?.data_dir {
+        return Ok(dir);
+    }
+
+    let local = PathBuf::from("data");
+    if local.is_dir() {
+        Ok(local)
+    } else {
+        Ok(Path::new(env!("CARGO_MANIFEST_DIR")).join("data"))
+    }
+}
+
+/// Normalize line endings to `\n`, strip trailing whitespace from every line
+/// and end the input with exactly one newline
+pub fn normalize(input: &str) -> String {
+    let mut normalized = String::with_capacity(input.len());
+
+    for line in input.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+
+    let len = normalized.trim_end().len();
+    normalized.truncate(len);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+
+    normalized
+}
+
+/// Read and normalize an input file
+pub fn read_input(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+
+    fs::read_to_string(path)
+        .map(|contents| normalize(&contents))
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+/// Path of a day's real input
+pub fn real_path(day: u32) -> io::Result<PathBuf> {
+    Ok(data_dir()?.join(format!("real/day{}.txt", day)))
+}
+
+/// Directory holding a day's examples
+pub fn example_dir(day: u32) -> io::Result<PathBuf> {
+    Ok(data_dir()?.join(format!("test/day{}", day)))
+}
+
+/// Example input from a puzzle description
+///
+/// Stored as `NAME.txt` in the day's example directory, next to `NAME.part1`
+/// and `NAME.part2` holding the expected answers. A part without an answer
+/// file is not checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    /// Expected answer of a part, normalized like an input
+    pub fn expected(&self, part: u32) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Load every example of a day, sorted by name with numbered examples
+/// first, in the order of their numbers
+pub fn examples(day: u32) -> io::Result<Vec<Example>> {
+    let dir = example_dir(day)?;
+
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", dir.display(), e)))?;
+
+    let mut names = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            if let Some(name) = path.file_stem().and_then(|name| name.to_str()) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    sort_names(&mut names);
+
+    let answer = |name: &str, part: u32| {
+        let path = dir.join(format!("{}.part{}", name, part));
+        if path.is_file() {
+            read_input(path).map(Some)
+        } else {
+            Ok(None)
+        }
+    };
+
+    names
+        .into_iter()
+        .map(|name| {
+            Ok(Example {
+                input: read_input(dir.join(format!("{}.txt", name)))?,
+                part1: answer(&name, 1)?,
+                part2: answer(&name, 2)?,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Load day input from input folder
+pub fn get_real(day: u32) -> io::Result<String> {
+    read_input(real_path(day)?)
+}
+
+/// Sort example names, numbers before other names and by their value so
+/// `2` comes before `10`
+fn sort_names(names: &mut [String]) {
+    names.sort_by(|a, b| match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    });
+}
+
+/// Load the first of a day's examples, `1.txt` when it is numbered
+pub fn get_test(day: u32) -> io::Result<String> {
+    examples(day)?
+        .into_iter()
+        .next()
+        .map(|example| example.input)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no examples for day {}", day),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_input() {
+        assert_eq!(normalize("a\r\nb  \r\n\r\nc\t\n\n\n"), "a\nb\n\nc\n");
+        assert_eq!(normalize(" 1  2\n 3  4"), " 1  2\n 3  4\n");
+        assert_eq!(normalize("\n\n"), "");
+    }
+
+    #[test]
+    fn missing_input() {
+        let error = get_real(0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("day0.txt"));
+
+        let error = get_test(0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn load_examples() {
+        let examples = examples(12).unwrap();
+        let names: Vec<_> = examples.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["1", "2", "3"]);
+        assert_eq!(examples[0].expected(1), Some("226\n"));
+        assert_eq!(examples[0].expected(2), Some("3509\n"));
+        assert_eq!(examples[0].expected(3), None);
+        assert_eq!(get_test(12).unwrap(), examples[0].input);
+
+        let mut names = ["b", "10", "2", "a", "1"].map(String::from);
+        sort_names(&mut names);
+        assert_eq!(names, ["1", "2", "10", "a", "b"]);
+    }
+}
@@ -0,0 +1,342 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Error from parsing a day's puzzle input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    /// Line of the offending text, starting at 1
+    pub line: usize,
+    /// Column of the offending text in characters, starting at 1
+    pub column: usize,
+    /// Offending text, empty when the input ended too early
+    pub text: String,
+    /// What was expected instead
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+
+        if self.text.is_empty() {
+            write!(f, " (found end of line)")
+        } else {
+            write!(f, " (found `{}`)", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Puzzle input being parsed, used to point errors at the offending text
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    day: u32,
+    input: &'a str,
+    /// Line number of the first line of `input` in the whole puzzle input
+    first_line: usize,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: u32, input: &'a str) -> Source<'a> {
+        Source::at_line(day, input, 1)
+    }
+
+    /// Source of a piece of the puzzle input starting on line `first_line`,
+    /// for inputs read one line at a time
+    pub fn at_line(day: u32, input: &'a str, first_line: usize) -> Source<'a> {
+        Source {
+            day,
+            input,
+            first_line,
+        }
+    }
+
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.input.lines()
+    }
+
+    /// Byte offset of `at` in the input, `at` should be a slice of the input
+    fn offset(&self, at: &str) -> usize {
+        let start = self.input.as_ptr() as usize;
+        let position = at.as_ptr() as usize;
+
+        if position >= start && position + at.len() <= start + self.input.len() {
+            position - start
+        } else {
+            self.input.find(at).unwrap_or(0)
+        }
+    }
+
+    /// Build an error pointing at `at`, a slice of the input
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        let offset = self.offset(at);
+        let line_start = self.input[..offset].rfind('\n').map_or(0, |i| i + 1);
+
+        ParseError {
+            day: self.day,
+            line: self.input[..offset].matches('\n').count() + self.first_line,
+            column: self.input[line_start..offset].chars().count() + 1,
+            text: at.lines().next().unwrap_or("").to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Build an error pointing just past the end of `at`
+    pub fn error_after(&self, at: &'a str, message: impl Into<String>) -> ParseError {
+        self.error(&at[at.len()..], message)
+    }
+
+    /// Unwrap a token that may be missing from `context`
+    pub fn expect(
+        &self,
+        token: Option<&'a str>,
+        context: &'a str,
+        what: &str,
+    ) -> Result<&'a str, ParseError> {
+        token.ok_or_else(|| self.error_after(context, format!("expected {}", what)))
+    }
+
+    /// Check every character of `text`, pointing at the first invalid one
+    pub fn check_chars(
+        &self,
+        text: &'a str,
+        valid: impl Fn(char) -> bool,
+        message: &str,
+    ) -> Result<(), ParseError> {
+        match text.char_indices().find(|(_, c)| !valid(*c)) {
+            Some((i, c)) => Err(self.error(&text[i..i + c.len_utf8()], message)),
+            None => Ok(()),
+        }
+    }
+
+    /// Parse a token as a number
+    pub fn number<T: FromStr>(&self, token: &'a str) -> Result<T, ParseError> {
+        token
+            .parse()
+            .map_err(|_| self.error(token, "expected a number"))
+    }
+
+    /// Parse every line of `text` with `f`, stopping at the first error
+    pub fn parse_lines<T>(
+        &self,
+        text: &'a str,
+        f: impl FnMut(&'a str) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        text.lines().map(f).collect()
+    }
+
+    /// Split `text` into blocks of lines separated by blank lines
+    pub fn blocks(&self, text: &'a str) -> Vec<&'a str> {
+        let mut blocks = vec![];
+        let mut block: Option<(usize, usize)> = None;
+        let mut offset = 0;
+
+        for line in text.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            if content.trim().is_empty() {
+                if let Some((start, end)) = block.take() {
+                    blocks.push(&text[start..end]);
+                }
+            } else {
+                let start = block.map_or(offset, |(start, _)| start);
+                block = Some((start, offset + content.len()));
+            }
+            offset += line.len();
+        }
+
+        if let Some((start, end)) = block {
+            blocks.push(&text[start..end]);
+        }
+        blocks
+    }
+
+    /// Split `text` into exactly `N` blocks, `names` saying what each one
+    /// holds when it is missing
+    pub fn sections<const N: usize>(
+        &self,
+        text: &'a str,
+        names: [&str; N],
+    ) -> Result<[&'a str; N], ParseError> {
+        let blocks = self.blocks(text);
+
+        if let Some(extra) = blocks.get(N) {
+            return Err(self.error(extra, "expected end of input"));
+        }
+        if let Some(missing) = names.get(blocks.len()) {
+            let end = blocks.last().copied().unwrap_or(text);
+            return Err(self.error_after(end, format!("expected {}", missing)));
+        }
+
+        Ok(blocks.try_into().unwrap())
+    }
+
+    /// Split `text` around the first `separator`, `shape` showing the whole
+    /// of it in errors
+    pub fn split_pair(
+        &self,
+        text: &'a str,
+        separator: &str,
+        shape: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        text.split_once(separator)
+            .ok_or_else(|| self.error(text, format!("expected `{}`", shape)))
+    }
+
+    /// Remove a literal `prefix` from `text`
+    pub fn prefix(&self, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        text.strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, format!("expected `{}`", prefix.trim())))
+    }
+
+    /// Parse items separated by `separator` with `f`, each stripped of the
+    /// whitespace around it
+    pub fn list<T>(
+        &self,
+        text: &'a str,
+        separator: char,
+        f: impl FnMut(&'a str) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        text.split(separator).map(str::trim).map(f).collect()
+    }
+
+    /// Parse numbers separated by `separator`, ignoring whitespace around
+    /// each number
+    pub fn numbers<T: FromStr>(
+        &self,
+        text: &'a str,
+        separator: char,
+    ) -> Result<Vec<T>, ParseError> {
+        self.list(text, separator, |number| self.number(number))
+    }
+
+    /// Parse numbers separated by any amount of whitespace
+    pub fn number_fields<T: FromStr>(&self, text: &'a str) -> Result<Vec<T>, ParseError> {
+        text.split_whitespace()
+            .map(|number| self.number(number))
+            .collect()
+    }
+
+    /// Check that `text` held exactly `N` items, `what` describing them in
+    /// errors
+    pub fn exactly<T, const N: usize>(
+        &self,
+        text: &'a str,
+        items: Vec<T>,
+        what: &str,
+    ) -> Result<[T; N], ParseError> {
+        items
+            .try_into()
+            .map_err(|_| self.error(text, format!("expected {}", what)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_position() {
+        let input = "12\n3x4\n";
+        let source = Source::new(1, input);
+
+        let line = input.lines().nth(1).unwrap();
+        let error = source.error(&line[1..2], "expected a digit");
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 2);
+        assert_eq!(error.text, "x");
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 2, column 2: expected a digit (found `x`)"
+        );
+    }
+
+    #[test]
+    fn error_at_end_of_line() {
+        let input = "forward\n";
+        let source = Source::new(2, input);
+
+        let line = input.lines().next().unwrap();
+        let error = source.expect(None, line, "an amount").unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 8));
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 1, column 8: expected an amount (found end of line)"
+        );
+    }
+
+    #[test]
+    fn number() {
+        let source = Source::new(1, "42 abc");
+        assert_eq!(source.number::<u32>(&source.input()[..2]), Ok(42));
+
+        let error = source.number::<u32>(&source.input()[3..]).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.text, "abc");
+    }
+
+    #[test]
+    fn blocks() {
+        let input = "a\nb\n\nc\n\n\nd\ne\n";
+        let source = Source::new(1, input);
+        assert_eq!(source.blocks(input), ["a\nb", "c", "d\ne"]);
+        assert_eq!(source.blocks("\n\n"), Vec::<&str>::new());
+
+        let [head, tail] = source.sections(&input[..6], ["head", "tail"]).unwrap();
+        assert_eq!((head, tail), ("a\nb", "c"));
+
+        let error = source.sections(&input[..4], ["head", "tail"]).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.message, "expected tail");
+
+        let error = source.sections(input, ["head", "tail"]).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (7, "d"));
+    }
+
+    #[test]
+    fn pieces() {
+        let input = "3,4 -> 5\nfold along y=7\n1  2 3\n";
+        let source = Source::new(1, input);
+        let lines: Vec<_> = input.lines().collect();
+
+        let (left, right) = source.split_pair(lines[0], " -> ", "a -> b").unwrap();
+        assert_eq!(source.numbers::<u32>(left, ','), Ok(vec![3, 4]));
+        assert_eq!(source.number::<u32>(right), Ok(5));
+
+        let error = source.split_pair(lines[1], " -> ", "a -> b").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected `a -> b`")
+        );
+
+        assert_eq!(source.prefix(lines[1], "fold along "), Ok("y=7"));
+        let error = source.prefix(lines[2], "fold along ").unwrap_err();
+        assert_eq!(error.message, "expected `fold along`");
+
+        let fields = source.number_fields::<u32>(lines[2]).unwrap();
+        assert_eq!(
+            source.exactly(lines[2], fields.clone(), "3 numbers"),
+            Ok([1, 2, 3])
+        );
+        let error = source
+            .exactly::<_, 2>(lines[2], fields, "2 numbers")
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let error = source.numbers::<u32>(left, ';').unwrap_err();
+        assert_eq!(error.text, "3,4");
+
+        let parsed = source.parse_lines(input, |line| Ok(line.len()));
+        assert_eq!(parsed, Ok(vec![8, 14, 6]));
+    }
+}
@@ -0,0 +1,115 @@
+use std::ops::RangeInclusive;
+
+/// Small deterministic random number generator (SplitMix64)
+///
+/// Not suitable for anything but test data, but the same seed gives the same
+/// numbers on every platform and version.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Number in `0..n`
+    ///
+    /// Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot pick a number below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Index into a slice of `len` items
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// Number in an inclusive range
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+
+        let span = end.wrapping_sub(start) as u64;
+        match span.checked_add(1) {
+            Some(n) => start.wrapping_add(self.below(n) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    /// True `numerator` times out of `denominator`
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Shuffle `items` in place, every order being equally likely
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        // Reference output of SplitMix64 seeded with 0
+        assert_eq!(Rng::new(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 5];
+
+        for _ in 0..200 {
+            let n = rng.range(-2..=2);
+            assert!((-2..=2).contains(&n));
+            seen[(n + 2) as usize] = true;
+        }
+        assert_eq!(seen, [true; 5]);
+
+        assert_eq!(rng.range(3..=3), 3);
+        rng.range(i64::MIN..=i64::MAX);
+        assert!(!rng.chance(0, 3));
+        assert!(rng.chance(3, 3));
+    }
+
+    #[test]
+    fn shuffle() {
+        let mut rng = Rng::new(2);
+        let mut items: Vec<_> = (0..20).collect();
+
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
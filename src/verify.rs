@@ -0,0 +1,323 @@
+use crate::{data_dir, days::Puzzle, get_real, normalize, Example, Solution};
+use std::{collections::BTreeMap, fmt, fs, io};
+
+/// File in the data directory holding the answers to the real inputs
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// Known answers to the real inputs
+///
+/// The file holds one `DAY PART ANSWER` line per part, `#` starts a comment:
+///
+/// ```text
+/// # day part answer
+/// 1 1 1466
+/// 13 2 EFJKZLBL
+/// ```
+///
+/// Glyph answers are written as the letters they spell.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    answers: BTreeMap<(u32, u32), String>,
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> Result<Manifest, String> {
+        let mut answers = BTreeMap::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let mut number = |what: &str| {
+                fields
+                    .next()
+                    .and_then(|field| field.trim().parse::<u32>().ok())
+                    .ok_or_else(|| format!("line {}: expected a {}", i + 1, what))
+            };
+            let day = number("day")?;
+            let part = number("part").and_then(|part| match part {
+                1 | 2 => Ok(part),
+                _ => Err(format!("line {}: expected part 1 or 2", i + 1)),
+            })?;
+            let answer = fields
+                .next()
+                .map(str::trim)
+                .filter(|answer| !answer.is_empty())
+                .ok_or_else(|| format!("line {}: expected an answer", i + 1))?;
+
+            if answers.insert((day, part), answer.to_string()).is_some() {
+                return Err(format!(
+                    "line {}: day {} part {} is listed twice",
+                    i + 1,
+                    day,
+                    part
+                ));
+            }
+        }
+
+        Ok(Manifest { answers })
+    }
+
+    /// Load the manifest kept in the data directory, empty when there is none
+    pub fn load() -> io::Result<Manifest> {
+        let path = data_dir()?.join(ANSWERS_FILE);
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Manifest::default()),
+            Err(e) => {
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("{}: {}", path.display(), e),
+                ))
+            }
+        };
+
+        Manifest::parse(&text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), e),
+            )
+        })
+    }
+
+    /// Known answer of a part, `None` while it is pending
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Compare what a part was solved to, in its [`manifest_form`], or why it
+    /// could not be solved against the known answer
+    pub fn check(&self, day: u32, part: u32, found: Result<String, String>) -> Outcome {
+        match (self.get(day, part), found) {
+            (_, Err(e)) => Outcome::Error(e),
+            (None, Ok(found)) => Outcome::Pending(found),
+            (Some(expected), Ok(found)) if expected == found => Outcome::Pass,
+            (Some(expected), Ok(found)) => Outcome::Fail {
+                expected: expected.to_string(),
+                found,
+            },
+        }
+    }
+}
+
+/// Solution as it is written in the manifest
+pub fn manifest_form(solution: &Solution) -> String {
+    match solution {
+        Ok(answer) => answer.submission().unwrap_or_else(|| answer.to_string()),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Result of checking one part against the manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        found: String,
+    },
+    /// No known answer yet, with what the solver found
+    Pending(String),
+    /// The input could not be read or parsed
+    Error(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "ok"),
+            Outcome::Fail { expected, found } => {
+                write!(f, "FAILED, expected {} but found {}", expected, found)
+            }
+            Outcome::Pending(found) => write!(f, "pending, found {:?}", found),
+            Outcome::Error(e) => write!(f, "error: {}", e),
+        }
+    }
+}
+
+/// Outcome of one day and part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+}
+
+/// Solve both parts of each puzzle on its real input and compare against the
+/// manifest
+pub fn verify(manifest: &Manifest, puzzles: &[Puzzle]) -> Vec<Check> {
+    let mut checks = vec![];
+
+    for puzzle in puzzles {
+        let parsed = get_real(puzzle.day)
+            .map_err(|e| e.to_string())
+            .and_then(|input| puzzle.parse(&input).map_err(|e| e.to_string()));
+
+        for part in [1, 2] {
+            let found = match &parsed {
+                Ok(parsed) => Ok(manifest_form(&puzzle.run(part, parsed).unwrap())),
+                Err(e) => Err(e.clone()),
+            };
+
+            checks.push(Check {
+                day: puzzle.day,
+                part,
+                outcome: manifest.check(puzzle.day, part, found),
+            });
+        }
+    }
+
+    checks
+}
+
+/// Outcome of one part of an example
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleCheck {
+    pub name: String,
+    pub part: u32,
+    pub outcome: Outcome,
+}
+
+/// Solve each example and compare against its expected answers, skipping the
+/// parts that have none
+///
+/// Answers are compared in the form they are displayed, so glyphs are drawn
+/// rather than read as letters. An example can also expect a parse error or
+/// no solution by its message.
+pub fn check_examples(puzzle: &Puzzle, examples: &[Example]) -> Vec<ExampleCheck> {
+    let mut checks = vec![];
+
+    for example in examples {
+        let parsed = puzzle.parse(&example.input);
+
+        for part in [1, 2] {
+            let expected = match example.expected(part) {
+                Some(expected) => expected,
+                None => continue,
+            };
+
+            // Normalized like the expected answers, which were read as inputs
+            let found = normalize(&match &parsed {
+                Ok(parsed) => match puzzle.run(part, parsed).unwrap() {
+                    Ok(answer) => answer.to_string(),
+                    Err(e) => e.to_string(),
+                },
+                Err(e) => e.to_string(),
+            });
+
+            let outcome = if found == expected {
+                Outcome::Pass
+            } else {
+                Outcome::Fail {
+                    expected: expected.to_string(),
+                    found,
+                }
+            };
+
+            checks.push(ExampleCheck {
+                name: example.name.clone(),
+                part,
+                outcome,
+            });
+        }
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let manifest = Manifest::parse("# comment\n1 1 1466\n13 2 EFJKZLBL # letters\n").unwrap();
+        assert_eq!(manifest.get(1, 1), Some("1466"));
+        assert_eq!(manifest.get(13, 2), Some("EFJKZLBL"));
+        assert_eq!(manifest.get(1, 2), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Manifest::parse("1 1").unwrap_err(),
+            "line 1: expected an answer"
+        );
+        assert_eq!(
+            Manifest::parse("\n1 3 12").unwrap_err(),
+            "line 2: expected part 1 or 2"
+        );
+        assert_eq!(
+            Manifest::parse("one 1 12").unwrap_err(),
+            "line 1: expected a day"
+        );
+        assert_eq!(
+            Manifest::parse("1 1 12\n1 1 13").unwrap_err(),
+            "line 2: day 1 part 1 is listed twice"
+        );
+    }
+
+    #[test]
+    fn outcomes() {
+        let manifest = Manifest::parse("1 1 1466\n1 2 1").unwrap();
+        let checks = verify(&manifest, &crate::days::REGISTRY[..1]);
+
+        assert_eq!(checks[0].outcome, Outcome::Pass);
+        assert_eq!(
+            checks[1].outcome,
+            Outcome::Fail {
+                expected: "1".to_string(),
+                found: "1491".to_string()
+            }
+        );
+
+        let checks = verify(&Manifest::default(), &crate::days::REGISTRY[..1]);
+        assert_eq!(checks[0].outcome, Outcome::Pending("1466".to_string()));
+    }
+
+    #[test]
+    fn example_outcomes() {
+        let example = |part1: &str, part2: Option<&str>| Example {
+            name: "1".to_string(),
+            input: "199\n200\n208\n210\n".to_string(),
+            part1: Some(part1.to_string()),
+            part2: part2.map(str::to_string),
+        };
+        let puzzle = &crate::days::REGISTRY[0];
+
+        let checks = check_examples(puzzle, &[example("3\n", None), example("2\n", None)]);
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].outcome, Outcome::Pass);
+        assert_eq!(
+            checks[1].outcome,
+            Outcome::Fail {
+                expected: "2\n".to_string(),
+                found: "3\n".to_string()
+            }
+        );
+
+        // Expected answers are read like inputs, with a final newline
+        let no_co2_bit = Example {
+            name: "1".to_string(),
+            input: "011\n011\n".to_string(),
+            part1: None,
+            part2: Some(normalize("no solution")),
+        };
+        let checks = check_examples(crate::days::get(3).unwrap(), &[no_co2_bit]);
+        assert_eq!(checks[0].outcome, Outcome::Pass);
+
+        let unparsable = Example {
+            input: "199\nx\n".to_string(),
+            part1: Some(normalize(
+                "day 1, line 2, column 1: expected a number (found `x`)",
+            )),
+            ..example("", None)
+        };
+        let checks = check_examples(puzzle, &[unparsable]);
+        assert_eq!(checks[0].outcome, Outcome::Pass);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{example_dir, real_path};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// How often watched files are checked for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Files a day is made of: its solver, its examples and its real input
+pub fn day_paths(src_dir: &Path, day: u32) -> io::Result<Vec<PathBuf>> {
+    Ok(vec![
+        src_dir.join(format!("days/day{}.rs", day)),
+        example_dir(day)?,
+        real_path(day)?,
+    ])
+}
+
+/// Files and directories polled for changes, without any OS notifications
+///
+/// A directory stands for the files directly in it. A path that does not
+/// exist yet is watched for being created.
+#[derive(Debug, Clone)]
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    stamps: BTreeMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        let stamps = stamps(&paths);
+        Watcher { paths, stamps }
+    }
+
+    /// Whether any watched file was changed, created or removed since the
+    /// watcher was made or last asked
+    pub fn changed(&mut self) -> bool {
+        let stamps = stamps(&self.paths);
+        let changed = stamps != self.stamps;
+        self.stamps = stamps;
+        changed
+    }
+}
+
+/// Modification time and length of every watched file, the length catching
+/// writes too close together for the file system's clock
+fn stamps(paths: &[PathBuf]) -> BTreeMap<PathBuf, (SystemTime, u64)> {
+    let mut stamps = BTreeMap::new();
+    let mut add = |path: PathBuf| {
+        if let Ok(metadata) = fs::metadata(&path) {
+            if metadata.is_file() {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                stamps.insert(path, (modified, metadata.len()));
+            }
+        }
+    };
+
+    for path in paths {
+        match fs::read_dir(path) {
+            Ok(entries) => entries.flatten().for_each(|entry| add(entry.path())),
+            Err(_) => add(path.clone()),
+        }
+    }
+
+    stamps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs::File, process};
+
+    #[test]
+    fn detects_changes() {
+        let dir = env::temp_dir().join(format!("advent2021-watch-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("examples")).unwrap();
+
+        let input = dir.join("input.txt");
+        fs::write(&input, "1\n").unwrap();
+        let mut watcher = Watcher::new(vec![input.clone(), dir.join("examples")]);
+        assert!(!watcher.changed());
+
+        // Same length, so only the modification time tells
+        fs::write(&input, "2\n").unwrap();
+        File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        let example = dir.join("examples/1.txt");
+        fs::write(&example, "1\n").unwrap();
+        assert!(watcher.changed());
+        fs::write(&example, "1\n2\n").unwrap();
+        assert!(watcher.changed());
+
+        fs::remove_file(&input).unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+        fs::write(&input, "3\n").unwrap();
+        assert!(watcher.changed());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}